@@ -4,7 +4,32 @@ use std::io::{Read, Write};
 use strfmt;
 use translation_provider::generate_translation;
 
-generate_translation! {
+#[path = "src/placeholders.rs"]
+mod placeholders;
+use placeholders::placeholders;
+
+// the keys are given once, for the struct and for the checks of the translation files
+macro_rules! declare_translations {
+    ($($key:ident,)* ; $($formatted_key:ident($($arg:ident: String),*),)*) => {
+        generate_translation! {
+            $($key,)*
+            $($formatted_key($($arg: String),*),)*
+        }
+
+        // every field of the generated struct
+        const KEYS: &[&str] = &[
+            $(stringify!($key),)*
+            $(stringify!($formatted_key),)*
+        ];
+
+        // the placeholders the formatted keys are declared with
+        const FORMATTED_KEYS: &[(&str, &[&str])] = &[
+            $((stringify!($formatted_key), &[$(stringify!($arg)),*]),)*
+        ];
+    };
+}
+
+declare_translations! {
     id,
     display,
 
@@ -21,11 +46,11 @@ generate_translation! {
     november,
     december,
 
-    restart_required_info,
-
     budget_category_header,
     budget_amount_header,
     budget_balance_header,
+    budget_parent_header,
+    no_parent_budget_category,
 
     spending_name_header,
     spending_budget_category_header,
@@ -33,15 +58,284 @@ generate_translation! {
     spending_day_header,
     spending_name_placeholder,
     spending_category_name_placeholder,
+    split_spending_button,
+    split_memo_placeholder,
+    spending_tags_header,
+    spending_note_header,
+    tags_report_button,
+    tag_filter_placeholder,
+    close_button,
+    payees_button,
+    payee_name_header,
+    payee_aliases_header,
+    payee_default_category_header,
+    payee_name_placeholder,
+    rules_button,
+    reapply_rules_button,
+    rule_priority_header,
+    rule_name_pattern_header,
+    rule_min_amount_header,
+    rule_max_amount_header,
+    spending_suggestion_header,
+    accept_suggestions_button,
+    merge_duplicates_button,
+    dismiss_duplicates_button,
+    spending_status_header,
+    status_cleared,
+    status_reconciled,
+    reconcile_button,
+    reconcile_end_date,
+    reconcile_closing_balance,
+    reconcile_cleared_header,
+    reconcile_finish_button,
+    spending_date_header,
+    budget_period_button,
+    budget_period_calendar_month,
+    budget_period_month_starting_on,
+    budget_period_weekly,
+    budget_period_bi_weekly,
+    budget_period_start_day,
+    budget_period_anchor,
+    goals_button,
+    goal_name_header,
+    goal_target_header,
+    goal_deadline_header,
+    goal_name_placeholder,
+    forecast_button,
+    forecast_months,
+    forecast_month_header,
+    forecast_income_header,
+    forecast_expenses_header,
+    forecast_balance_header,
+    net_worth_button,
+    holding_name_header,
+    holding_kind_header,
+    holding_name_placeholder,
+    holding_kind_account,
+    holding_kind_property,
+    holding_kind_investment,
+    holding_kind_loan,
+    holding_kind_other_liability,
+    valuation_holding_header,
+    valuation_value_header,
+    net_worth_assets_header,
+    net_worth_liabilities_header,
+    net_worth_header,
+    loans_button,
+    loan_name_header,
+    loan_principal_header,
+    loan_rate_header,
+    loan_term_header,
+    loan_start_header,
+    loan_payment_day_header,
+    loan_monthly_payment_header,
+    loan_name_placeholder,
+    loan_schedule_number_header,
+    loan_schedule_payment_header,
+    loan_schedule_interest_header,
+    loan_schedule_principal_header,
+    loan_schedule_extra_header,
+    loan_schedule_remaining_header,
+    loan_extra_payment_button,
+    members_button,
+    member_name_header,
+    member_name_placeholder,
+    no_member,
+    spending_paid_by_header,
+    spending_share_header,
+    share_equal,
+    settle_up_button,
+    settle_up_from,
+    settle_up_to,
+    settle_up_balance_header,
+    settle_up_transfers_header,
+    spending_reimbursement_header,
+    not_reimbursable,
+    reimbursement_pending,
+    reimbursement_submitted,
+    reimbursement_reimbursed,
+    reimbursements_button,
+    reimbursements_outstanding_header,
+    reimbursements_incomes_header,
+    reimbursement_mark_submitted_button,
+    reimbursement_link_button,
+    reimbursement_export_button,
+    reimbursement_csv_date,
+    reimbursement_csv_name,
+    reimbursement_csv_category,
+    reimbursement_csv_amount,
+    reimbursement_csv_status,
+    reimbursement_csv_note,
+    alerts_button,
+    alerts_notifications,
+    alerts_nearly_spent_percent,
+    alerts_large_transaction,
+    alert_header,
+    bills_button,
+    bill_name_header,
+    bill_amount_header,
+    bill_due_date_header,
+    bill_repeat_header,
+    bill_status_header,
+    bill_name_placeholder,
+    bill_repeat_once,
+    bill_repeat_monthly,
+    bill_repeat_yearly,
+    bill_status_paid,
+    bill_status_due,
+    bill_status_overdue,
+    bills_upcoming_header,
+    bill_mark_paid_button,
+    search_placeholder,
+    search_title,
+    search_regex,
+    search_any_category,
+    search_min_amount,
+    search_max_amount,
+    search_from,
+    search_to,
+    search_month_header,
+    spendings_filter_placeholder,
 
     decimal_separator,
     thousands_separator,
 
+    zero_based_budgeting,
+    move_money_button,
+    move_money_from,
+    move_money_to,
+    move_money_amount,
+    ok_button,
+    cancel_button,
+    ;
+
     format_money(sign: String, whole: String, cents: String),
     whole_balance(sign: String, whole: String, cents: String),
+    ready_to_assign(sign: String, whole: String, cents: String),
+    envelope_transfer_entry(day: String, from: String, to: String, amount: String),
+    tag_total(tag: String, amount: String),
+    category_suggestion(category: String, confidence: String),
+    reconcile_cleared_balance(amount: String),
+    reconcile_difference(amount: String),
+    budget_period_range(start: String, end: String),
+    goal_progress(name: String, saved: String, target: String, monthly: String),
+    goal_behind_schedule(expected: String),
+    forecast_lowest_point(amount: String, date: String),
+    loan_payment_note(interest: String, principal: String),
+    loan_extra_payment_name(name: String),
+    settle_up_transfer(from: String, to: String, amount: String),
+    reimbursements_total(amount: String),
+    reimbursement_export_failed(error: String),
+    alerts_button_count(count: String),
+    alert_nearly_spent(category: String, percent: String),
+    alert_over_budget(category: String, spent: String, budget: String),
+    alert_negative_total(total: String),
+    alert_large_spending(name: String, amount: String, date: String),
+    search_result_count(count: String),
+}
+
+// the language the other ones fall back to
+const FALLBACK_LANGUAGE_FILE: &str = "translations/en_GB.json";
+
+// the errors of a file: the keys missing or unknown compared to the generated struct, and the
+// formatted strings not using exactly their declared placeholders
+fn check_translation(
+    file_name: &str,
+    translation: &serde_json::Map<String, serde_json::Value>,
+    keys: &[&str],
+) -> Vec<String> {
+    let mut errors = Vec::new();
+    for key in keys {
+        if !translation
+            .get(*key)
+            .map_or(false, serde_json::Value::is_string)
+        {
+            errors.push(format!("{}: missing key {}", file_name, key));
+        }
+    }
+    for key in translation.keys() {
+        if !keys.contains(&key.as_str()) {
+            errors.push(format!("{}: unknown key {}", file_name, key));
+        }
+    }
+    for (key, declared) in FORMATTED_KEYS {
+        let format = match translation.get(*key).and_then(serde_json::Value::as_str) {
+            Some(format) => format,
+            None => continue,
+        };
+        let used = placeholders(format);
+        for placeholder in &used {
+            if !declared.contains(&placeholder.as_str()) {
+                errors.push(format!(
+                    "{}: {} uses the undeclared placeholder {{{}}}",
+                    file_name, key, placeholder
+                ));
+            }
+        }
+        for placeholder in declared.iter() {
+            if !used.iter().any(|used| used == placeholder) {
+                errors.push(format!(
+                    "{}: {} doesn't use the placeholder {{{}}}",
+                    file_name, key, placeholder
+                ));
+            }
+        }
+    }
+    errors
+}
+
+// every error is reported before the build fails, with how much of each language is translated
+// (the keys left the same as in the fallback language being counted as not translated)
+fn check_translations(fallback_content: &str, keys: &[&str]) {
+    let fallback: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(fallback_content).expect("Could not deserialize fallback file");
+    let mut errors = Vec::new();
+    for entry in std::fs::read_dir("translations").expect("Could not open translation directory") {
+        let path = entry.unwrap().path();
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        let content = std::fs::read_to_string(&path).expect("Could not read translation file");
+        let translation: serde_json::Map<String, serde_json::Value> =
+            match serde_json::from_str(&content) {
+                Ok(translation) => translation,
+                Err(e) => {
+                    errors.push(format!("{}: {}", file_name, e));
+                    continue;
+                }
+            };
+        errors.extend(check_translation(&file_name, &translation, keys));
+
+        let translated = keys
+            .iter()
+            .filter(|key| {
+                translation
+                    .get(**key)
+                    .map_or(false, serde_json::Value::is_string)
+                    && (path.ends_with(FALLBACK_LANGUAGE_FILE)
+                        || translation.get(**key) != fallback.get(**key))
+            })
+            .count();
+        println!(
+            "cargo:warning={}: {}/{} keys translated ({}%)",
+            file_name,
+            translated,
+            keys.len(),
+            translated * 100 / keys.len().max(1)
+        );
+    }
+
+    for error in &errors {
+        println!("cargo:warning={}", error);
+    }
+    if !errors.is_empty() {
+        panic!("{} error(s) in the translation files", errors.len());
+    }
 }
 
 fn main() {
+    let fallback_content = std::fs::read_to_string(FALLBACK_LANGUAGE_FILE)
+        .expect("Could not read the fallback translation file");
+    check_translations(&fallback_content, KEYS);
+
     let mut get_fn = quote! {};
     let mut get_language_list = quote! {};
     for entry in std::fs::read_dir("translations").expect("Could not open translation directory") {
@@ -76,28 +370,47 @@ fn main() {
         };
     }
 
+    // the runtime loaded languages are merged in by translation_provider.rs
     get_fn = quote! {
-            pub fn get_provider(locale_id: &str) -> Option<TranslationProvider> {
-                serde_json::from_str(match locale_id {
+            fn get_builtin_content(locale_id: &str) -> Option<&'static str> {
+                Some(match locale_id {
                         #get_fn
                         _ => return None,
-                }).expect("TranslationProvider construction failed - the build is corrupted!")
+                })
             }
     };
 
     get_language_list = quote! {
-        pub fn get_language_list() -> Vec<(String, String)> {
+        fn get_builtin_language_list() -> Vec<(String, String)> {
             vec![
                 #get_language_list
             ]
         }
     };
 
+    let keys = KEYS.iter();
+    let formatted_keys = FORMATTED_KEYS.iter().map(|(key, arguments)| {
+        let arguments = arguments.iter();
+        quote! { (#key, &[#(#arguments),*]) }
+    });
+
     let final_generated = quote! {
         impl TranslationProvider {
             #get_fn
 
             #get_language_list
+
+            fn get_fallback_content() -> &'static str {
+                #fallback_content
+            }
+
+            fn get_keys() -> &'static [&'static str] {
+                &[#(#keys),*]
+            }
+
+            fn get_formatted_keys() -> &'static [(&'static str, &'static [&'static str])] {
+                &[#(#formatted_keys),*]
+            }
         }
     };
 
@@ -0,0 +1,151 @@
+use crate::config::{AlertSettings, BudgetingMode};
+use crate::data::{BudgetAmount, BudgetCategories, BudgetCategoryId, MonthlyBudget};
+use chrono::NaiveDate;
+use log::debug;
+use std::collections::HashMap;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Alert {
+    // spent at least the configured share of the budget, but not all of it
+    NearlySpent {
+        budget_category_id: BudgetCategoryId,
+        percent: u32,
+    },
+    OverBudget {
+        budget_category_id: BudgetCategoryId,
+        spent: i32,
+        budget: i32,
+    },
+    // more was spent than earned during the period
+    NegativeTotal {
+        total: i32,
+    },
+    LargeTransaction {
+        name: String,
+        date: NaiveDate,
+        amount: i32,
+    },
+}
+
+impl Alert {
+    // the percentages change with every spending, an alert is only raised again when its kind
+    // changes
+    pub fn is_same_as(&self, other: &Alert) -> bool {
+        match (self, other) {
+            (
+                Alert::NearlySpent {
+                    budget_category_id: a,
+                    ..
+                },
+                Alert::NearlySpent {
+                    budget_category_id: b,
+                    ..
+                },
+            )
+            | (
+                Alert::OverBudget {
+                    budget_category_id: a,
+                    ..
+                },
+                Alert::OverBudget {
+                    budget_category_id: b,
+                    ..
+                },
+            ) => a == b,
+            (Alert::NegativeTotal { .. }, Alert::NegativeTotal { .. }) => true,
+            _ => self == other,
+        }
+    }
+}
+
+pub fn evaluate(
+    settings: &AlertSettings,
+    budget_categories: &BudgetCategories,
+    monthly_budget: &MonthlyBudget,
+    budgeting_mode: BudgetingMode,
+) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+
+    // same balance as the budget categories' view, without the budgets
+    let mut spent_per_budget = HashMap::new();
+    for spending in &monthly_budget.spendings.0 {
+        for (budget_category_id, amount) in spending.amounts_per_category() {
+            if budgeting_mode == BudgetingMode::ZeroBased && amount > 0 {
+                continue;
+            }
+            *spent_per_budget.entry(budget_category_id).or_insert(0) -= amount;
+        }
+    }
+    for budget_category_id in budget_categories.0.keys() {
+        let budget = monthly_budget
+            .budgets
+            .get(budget_category_id)
+            .unwrap_or(&BudgetAmount(0))
+            .0
+            + monthly_budget.net_transfers(*budget_category_id);
+        let spent = *spent_per_budget.get(budget_category_id).unwrap_or(&0);
+        if budget <= 0 || spent <= 0 {
+            continue;
+        }
+        let percent = (i64::from(spent) * 100 / i64::from(budget)) as u32;
+        if spent > budget {
+            alerts.push(Alert::OverBudget {
+                budget_category_id: *budget_category_id,
+                spent,
+                budget,
+            });
+        } else if percent >= settings.nearly_spent_percent {
+            alerts.push(Alert::NearlySpent {
+                budget_category_id: *budget_category_id,
+                percent,
+            });
+        }
+    }
+
+    let total = monthly_budget
+        .spendings
+        .0
+        .iter()
+        .map(|spending| spending.amount.to_i32())
+        .sum::<i32>();
+    if total < 0 {
+        alerts.push(Alert::NegativeTotal { total });
+    }
+
+    if settings.large_transaction > 0 {
+        for spending in &monthly_budget.spendings.0 {
+            if -spending.amount.to_i32() >= settings.large_transaction {
+                alerts.push(Alert::LargeTransaction {
+                    name: spending.name.clone(),
+                    date: spending.date,
+                    amount: spending.amount.to_i32(),
+                });
+            }
+        }
+    }
+    alerts
+}
+
+// through the freedesktop notification interface, by way of notify-send; not having it
+// installed only loses the desktop part, the alerts are still listed in the app
+// the process is waited for on its own thread, so that a slow notification daemon never blocks
+// the UI
+pub fn notify(summary: &str, body: &str) {
+    match Command::new("notify-send")
+        .arg("--app-name=Moneyz")
+        .arg(summary)
+        .arg(body)
+        .spawn()
+    {
+        Ok(mut child) => {
+            let body = body.to_owned();
+            std::thread::spawn(move || match child.wait() {
+                Ok(status) if status.success() => debug!("Sent notification: {}", body),
+                Ok(status) => debug!("Could not send the notification: {}", status),
+                Err(err) => debug!("Could not send the notification: {}", err),
+            });
+        }
+        Err(err) => debug!("Could not send the notification: {}", err),
+    }
+}
@@ -0,0 +1,137 @@
+use crate::data::{date_in_month, month_year_of, next_month, BudgetCategoryId};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// the bills due that many days from today are upcoming
+pub const UPCOMING_DAYS: i64 = 14;
+// the unpaid bills stay in the upcoming list for that long after their due date
+pub const OVERDUE_DAYS: i64 = 31;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum BillRepeat {
+    Once,
+    Monthly,
+    Yearly,
+}
+
+// in the order they are offered in the combo boxes
+pub const BILL_REPEATS: [BillRepeat; 3] =
+    [BillRepeat::Once, BillRepeat::Monthly, BillRepeat::Yearly];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Bill {
+    pub name: String,
+    // in cents, what is paid
+    pub amount: i32,
+    // the first due date, the next ones keep its day
+    pub due_date: NaiveDate,
+    pub repeat: BillRepeat,
+    #[serde(default)]
+    pub budget_category_id: Option<BudgetCategoryId>,
+    // the due dates which were paid
+    #[serde(default)]
+    pub paid: Vec<NaiveDate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Bills(pub Vec<Bill>);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BillStatus {
+    Paid,
+    Due,
+    Overdue,
+}
+
+// a due date of a bill
+pub struct BillOccurrence {
+    pub bill_idx: usize,
+    pub due_date: NaiveDate,
+    pub status: BillStatus,
+}
+
+impl Bill {
+    pub fn new(name: String, due_date: NaiveDate) -> Self {
+        Bill {
+            name,
+            amount: 0,
+            due_date,
+            repeat: BillRepeat::Monthly,
+            budget_category_id: None,
+            paid: Vec::new(),
+        }
+    }
+
+    // the due dates between from and to, both included
+    pub fn due_dates(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut due_dates = Vec::new();
+        let day = self.due_date.day();
+        let (mut m, mut y) = month_year_of(self.due_date);
+        let mut due_date = self.due_date;
+        while due_date <= to {
+            if from <= due_date {
+                due_dates.push(due_date);
+            }
+            match self.repeat {
+                BillRepeat::Once => break,
+                BillRepeat::Monthly => {
+                    let next = next_month(m, y);
+                    m = next.0;
+                    y = next.1;
+                }
+                BillRepeat::Yearly => y.0 += 1,
+            }
+            // the day is kept, down to the last one of shorter months
+            due_date = date_in_month(m, y, day);
+        }
+        due_dates
+    }
+
+    pub fn status(&self, due_date: NaiveDate, today: NaiveDate) -> BillStatus {
+        if self.paid.contains(&due_date) {
+            BillStatus::Paid
+        } else if due_date < today {
+            BillStatus::Overdue
+        } else {
+            BillStatus::Due
+        }
+    }
+}
+
+impl Bills {
+    // sorted by due date
+    pub fn occurrences(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        today: NaiveDate,
+    ) -> Vec<BillOccurrence> {
+        let mut occurrences = self
+            .0
+            .iter()
+            .enumerate()
+            .flat_map(|(bill_idx, bill)| {
+                bill.due_dates(from, to)
+                    .into_iter()
+                    .map(move |due_date| BillOccurrence {
+                        bill_idx,
+                        due_date,
+                        status: bill.status(due_date, today),
+                    })
+            })
+            .collect::<Vec<_>>();
+        occurrences.sort_by_key(|occurrence| (occurrence.due_date, occurrence.bill_idx));
+        occurrences
+    }
+
+    // the unpaid bills due soon, along with the recently missed ones
+    pub fn upcoming(&self, today: NaiveDate) -> Vec<BillOccurrence> {
+        let mut upcoming = self.occurrences(
+            today - Duration::days(OVERDUE_DAYS),
+            today + Duration::days(UPCOMING_DAYS),
+            today,
+        );
+        upcoming.retain(|occurrence| occurrence.status != BillStatus::Paid);
+        upcoming
+    }
+}
@@ -0,0 +1,120 @@
+use crate::data::{BudgetCategories, BudgetCategoryId, MonthlyBudget, Spending};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// pseudo token for the amount, so that e.g. a rent and a coffee from the same payee can be told
+// apart
+fn amount_bucket(amount: i32) -> String {
+    let sign = if amount < 0 { "-" } else { "+" };
+    // one bucket per order of magnitude of the amount in whole units
+    let mut whole = (amount / 100).abs();
+    let mut magnitude = 0;
+    while whole > 0 {
+        whole /= 10;
+        magnitude += 1;
+    }
+    format!("{}amount{}", sign, magnitude)
+}
+
+fn features(spending: &Spending) -> Vec<String> {
+    let mut features = spending
+        .name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.len() > 1)
+        .map(|token| token.to_lowercase())
+        .collect::<Vec<_>>();
+    features.push(amount_bucket(spending.amount.to_i32()));
+    features
+}
+
+#[derive(Default)]
+struct CategoryStatistics {
+    spendings: u32,
+    features: u32,
+    feature_counts: HashMap<String, u32>,
+}
+
+// multinomial naive Bayes over the tokens of the spendings' names and their amount
+#[derive(Default)]
+pub struct CategoryClassifier {
+    categories: BTreeMap<BudgetCategoryId, CategoryStatistics>,
+    vocabulary: HashSet<String>,
+    spendings: u32,
+}
+
+impl CategoryClassifier {
+    pub fn train<'a, I: IntoIterator<Item = &'a MonthlyBudget>>(
+        monthly_budgets: I,
+        budget_categories: &BudgetCategories,
+    ) -> Self {
+        let mut classifier = CategoryClassifier::default();
+        for monthly_budget in monthly_budgets {
+            for spending in &monthly_budget.spendings.0 {
+                // split spendings do not belong to a single category
+                if spending.splits.is_empty()
+                    && budget_categories
+                        .0
+                        .contains_key(&spending.budget_category_id)
+                {
+                    classifier.learn(spending);
+                }
+            }
+        }
+        classifier
+    }
+
+    pub fn learn(&mut self, spending: &Spending) {
+        let features = features(spending);
+        let statistics = self
+            .categories
+            .entry(spending.budget_category_id)
+            .or_default();
+        statistics.spendings += 1;
+        statistics.features += features.len() as u32;
+        for feature in features {
+            *statistics
+                .feature_counts
+                .entry(feature.clone())
+                .or_insert(0) += 1;
+            self.vocabulary.insert(feature);
+        }
+        self.spendings += 1;
+    }
+
+    // returns the most likely category along with its probability, between 0 and 1
+    pub fn suggest(&self, spending: &Spending) -> Option<(BudgetCategoryId, f64)> {
+        if self.spendings == 0 {
+            return None;
+        }
+
+        let features = features(spending);
+        let vocabulary_size = self.vocabulary.len() as f64;
+        let log_likelihoods = self
+            .categories
+            .iter()
+            .map(|(id, statistics)| {
+                let prior = (f64::from(statistics.spendings) / f64::from(self.spendings)).ln();
+                // Laplace smoothing, so that unseen tokens do not rule a category out
+                let denominator = f64::from(statistics.features) + vocabulary_size;
+                let likelihood = features
+                    .iter()
+                    .map(|feature| {
+                        let count = statistics.feature_counts.get(feature).map_or(0, |c| *c);
+                        ((f64::from(count) + 1.0) / denominator).ln()
+                    })
+                    .sum::<f64>();
+                (*id, prior + likelihood)
+            })
+            .collect::<Vec<_>>();
+
+        let (best_id, best_log_likelihood) = log_likelihoods
+            .iter()
+            .cloned()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())?;
+        // normalize the posterior without leaving the log space to avoid underflows
+        let total = log_likelihoods
+            .iter()
+            .map(|(_, log_likelihood)| (log_likelihood - best_log_likelihood).exp())
+            .sum::<f64>();
+        Some((best_id, 1.0 / total))
+    }
+}
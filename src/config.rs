@@ -1,12 +1,57 @@
+use crate::periods::BudgetPeriodKind;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum BudgetingMode {
+    // budgets are plain monthly limits, income is counted in the categories' balance
+    Classic,
+    // income goes to a "ready to assign" pool, which the budgets draw from
+    ZeroBased,
+}
+
+impl Default for BudgetingMode {
+    fn default() -> Self {
+        BudgetingMode::Classic
+    }
+}
+
+// thresholds of the alerts raised after each save
+#[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct AlertSettings {
+    pub notifications: bool,
+    // of the category's budget, 100% being over budget
+    pub nearly_spent_percent: u32,
+    // in cents, 0 disables the alert
+    pub large_transaction: i32,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        AlertSettings {
+            notifications: true,
+            nearly_spent_percent: 80,
+            large_transaction: 50000,
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Config {
     pub language: String,
+    #[serde(default)]
+    pub budgeting_mode: BudgetingMode,
+    #[serde(default)]
+    pub budget_period: BudgetPeriodKind,
+    #[serde(default)]
+    pub alerts: AlertSettings,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             language: "en_GB".to_owned(),
+            budgeting_mode: Default::default(),
+            budget_period: Default::default(),
+            alerts: Default::default(),
         }
     }
 }
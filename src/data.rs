@@ -1,8 +1,11 @@
+use chrono::{Datelike, NaiveDate};
 use num_derive::FromPrimitive;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(FromPrimitive, Copy, Clone)]
+#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq)]
 pub enum Month {
     Jan = 0,
     Feb,
@@ -24,6 +27,46 @@ impl Month {
     }
 }
 
+// how the dates are shown and typed in
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+// the 31st of a 30 days month becomes the 30th
+pub fn date_in_month(m: Month, y: Year, day: u32) -> NaiveDate {
+    (1..=day.max(1))
+        .rev()
+        .filter_map(|day| NaiveDate::from_ymd_opt(y.0 as i32, m.id() as u32, day))
+        .next()
+        .expect("Every month has a first day")
+}
+
+// i.e. which file the spendings of that date go to
+pub fn month_year_of(date: NaiveDate) -> (Month, Year) {
+    (
+        num_traits::FromPrimitive::from_u32(date.month0()).unwrap(),
+        Year(date.year() as u32),
+    )
+}
+
+pub fn previous_month(m: Month, y: Year) -> (Month, Year) {
+    match m {
+        Month::Jan => (Month::Dec, Year(y.0 - 1)),
+        _ => (
+            num_traits::FromPrimitive::from_i32(m as i32 - 1).unwrap(),
+            y,
+        ),
+    }
+}
+
+pub fn next_month(m: Month, y: Year) -> (Month, Year) {
+    match m {
+        Month::Dec => (Month::Jan, Year(y.0 + 1)),
+        _ => (
+            num_traits::FromPrimitive::from_i32(m as i32 + 1).unwrap(),
+            y,
+        ),
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Hash, Eq, PartialEq, Copy, Clone)]
 pub struct Year(pub u32);
 
@@ -58,7 +101,7 @@ pub struct BudgetCategory(pub String);
 // the choice of BTreeMap is ordered, therefore, we can easily generate a new UNIQUE id for each
 // category by increment the max index by one. Of course we *should* handle overflow but I doubt
 // someone's gonna create THOUSANDS OF CATEGORIES GODDAMMIT
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BudgetCategories(pub BTreeMap<BudgetCategoryId, BudgetCategory>);
 
 impl Default for BudgetCategories {
@@ -67,11 +110,207 @@ impl Default for BudgetCategories {
     }
 }
 
+// maps each sub-category to its parent; categories absent from the map are top-level ones
+// this lives next to the BudgetCategories (and not inside) to keep the existing files readable
 #[derive(Serialize, Deserialize, Debug)]
+pub struct BudgetCategoryParents(pub BTreeMap<BudgetCategoryId, BudgetCategoryId>);
+
+impl Default for BudgetCategoryParents {
+    fn default() -> Self {
+        BudgetCategoryParents(BTreeMap::new())
+    }
+}
+
+impl BudgetCategoryParents {
+    pub fn parent_of(&self, id: BudgetCategoryId) -> Option<BudgetCategoryId> {
+        self.0.get(&id).copied()
+    }
+
+    pub fn children_of(&self, id: BudgetCategoryId) -> Vec<BudgetCategoryId> {
+        self.0
+            .iter()
+            .filter(|(_, parent)| **parent == id)
+            .map(|(child, _)| *child)
+            .collect()
+    }
+
+    // true if ancestor is id itself, or any of its (grand-)parents
+    pub fn is_descendant_of(&self, id: BudgetCategoryId, ancestor: BudgetCategoryId) -> bool {
+        let mut current = Some(id);
+        while let Some(current_id) = current {
+            if current_id == ancestor {
+                return true;
+            }
+            current = self.parent_of(current_id);
+        }
+        false
+    }
+
+    pub fn set_parent(&mut self, id: BudgetCategoryId, parent: Option<BudgetCategoryId>) {
+        match parent {
+            Some(parent) => self.0.insert(id, parent),
+            None => self.0.remove(&id),
+        };
+    }
+
+    // the children of a removed category are moved one level up
+    pub fn remove(&mut self, id: BudgetCategoryId) {
+        let parent = self.0.remove(&id);
+        for child in self.children_of(id) {
+            self.set_parent(child, parent);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BudgetAmount(pub i32);
 
+#[derive(Serialize, Deserialize, Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PayeeId(pub u32);
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Payee {
+    pub name: String,
+    // other spellings of the same payee, e.g. "TESCO STORES" for "Tesco"
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // given to new spendings with this payee
+    #[serde(default)]
+    pub default_budget_category_id: Option<BudgetCategoryId>,
+}
+
+impl Payee {
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.trim().to_lowercase();
+        self.name.to_lowercase() == name
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.to_lowercase() == name)
+    }
+
+    pub fn aliases_to_string(&self) -> String {
+        self.aliases.join(", ")
+    }
+}
+
+// same as the BudgetCategories, new ids are the max one + 1
 #[derive(Serialize, Deserialize, Debug)]
+pub struct Payees(pub BTreeMap<PayeeId, Payee>);
+
+impl Default for Payees {
+    fn default() -> Self {
+        Payees(BTreeMap::new())
+    }
+}
+
+impl Payees {
+    // case insensitive, on the names as well as the aliases
+    pub fn find(&self, name: &str) -> Option<&Payee> {
+        self.0.values().find(|payee| payee.matches(name))
+    }
+
+    pub fn add(&mut self, name: String) -> PayeeId {
+        let new_id = PayeeId(self.0.keys().last().map_or(0, |id| id.0 + 1));
+        self.0.insert(
+            new_id,
+            Payee {
+                name,
+                aliases: Vec::new(),
+                default_budget_category_id: None,
+            },
+        );
+        new_id
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MemberId(pub u32);
+
+// someone sharing the budget, e.g. a flatmate
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Member {
+    pub name: String,
+}
+
+// same as the Payees, new ids are the max one + 1
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Members(pub BTreeMap<MemberId, Member>);
+
+impl Members {
+    // case insensitive
+    pub fn find(&self, name: &str) -> Option<MemberId> {
+        let name = name.trim().to_lowercase();
+        self.0
+            .iter()
+            .find(|(_, member)| member.name.to_lowercase() == name)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn add(&mut self, name: String) -> MemberId {
+        let new_id = MemberId(self.0.keys().last().map_or(0, |id| id.0 + 1));
+        self.0.insert(new_id, Member { name });
+        new_id
+    }
+}
+
+// how a shared spending is divided between the members
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ShareRule {
+    // between every member
+    Equal,
+    // in percent
+    Percentages(Vec<(MemberId, f64)>),
+    // in cents
+    ExactAmounts(Vec<(MemberId, i32)>),
+}
+
+impl Default for ShareRule {
+    fn default() -> Self {
+        ShareRule::Equal
+    }
+}
+
+// "TESCO STORES, tesco" -> ["TESCO STORES", "tesco"]
+pub fn parse_aliases(aliases: &str) -> Vec<String> {
+    aliases
+        .split(',')
+        .map(|alias| alias.trim().to_owned())
+        .filter(|alias| !alias.is_empty())
+        .collect()
+}
+
+// never reused, so that a spending is found again whatever the order of the rows or the file
+// it moved to
+// 0 is for the spendings written before the ids existed, the FileLoader gives them one
+#[derive(
+    Serialize, Deserialize, Debug, Default, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct SpendingId(pub u64);
+
+static LAST_SPENDING_ID: AtomicU64 = AtomicU64::new(0);
+
+impl SpendingId {
+    // the current time in nanoseconds, bumped when two spendings are created within the same one
+    pub fn new() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("The clock is set before 1970!")
+            .as_nanos() as u64;
+        let id = now.max(LAST_SPENDING_ID.load(Ordering::SeqCst) + 1);
+        LAST_SPENDING_ID.store(id, Ordering::SeqCst);
+        SpendingId(id)
+    }
+
+    pub fn is_assigned(self) -> bool {
+        self.0 != 0
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Spending {
+    #[serde(default)]
+    pub id: SpendingId,
     pub name: String,
     // TODO: need to use Option<T>
     // we use a flag for default with a dummy category value,
@@ -82,17 +321,176 @@ pub struct Spending {
     pub budget_category_id: BudgetCategoryId,
     pub budget_category_name: BudgetCategory,
     pub amount: MoneyAmount,
-    // only a day, for the month and year should be known for each MonthlyBudget anyway
-    pub day: Day,
+    // the month and year also tell which MonthlyBudget the spending belongs to
+    #[serde(default = "undated")]
+    pub date: NaiveDate,
+    // the files written before the full date was stored only have the day of the month, the
+    // FileLoader turns it into a date when loading them
+    #[serde(default, rename = "day", skip_serializing)]
+    pub legacy_day: Option<Day>,
+    // when not empty, the amount is spread across several categories
+    #[serde(default)]
+    pub splits: Vec<SpendingSplit>,
+    // stored without the leading '#'
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub note: String,
+    // the spendings the user told us this is not a duplicate of, even though it looks like one
+    #[serde(default)]
+    pub dismissed_duplicates: Vec<SpendingId>,
+    #[serde(default)]
+    pub status: ClearedStatus,
+    // only the spendings paid by a member are shared
+    #[serde(default)]
+    pub paid_by: Option<MemberId>,
+    #[serde(default)]
+    pub share_rule: ShareRule,
+    // None when the spending isn't a work expense
+    #[serde(default)]
+    pub reimbursement: Option<ReimbursementStatus>,
+    // shared by an expense and the income which paid it back
+    #[serde(default)]
+    pub reimbursement_link: Option<u32>,
+}
+
+// where a spending stands compared to the bank statements
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum ClearedStatus {
+    Uncleared,
+    // ticked off a statement, which hasn't been reconciled yet
+    Cleared,
+    // part of a finished reconciliation - it can't be edited anymore
+    Reconciled,
+}
+
+impl Default for ClearedStatus {
+    fn default() -> Self {
+        ClearedStatus::Uncleared
+    }
+}
+
+// where a reimbursable expense stands in the expense claim workflow
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum ReimbursementStatus {
+    Pending,
+    Submitted,
+    Reimbursed,
+}
+
+// in the order they are offered in the combo boxes
+pub const REIMBURSEMENT_STATUSES: [ReimbursementStatus; 3] = [
+    ReimbursementStatus::Pending,
+    ReimbursementStatus::Submitted,
+    ReimbursementStatus::Reimbursed,
+];
+
+// placeholder for the spendings of the old files, until they get their date from the legacy day
+fn undated() -> NaiveDate {
+    NaiveDate::from_ymd(1970, 1, 1)
+}
+
+// tags are case insensitive, and may be typed with or without their '#'
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+// "#vacation-2026, #reimbursable" -> ["vacation-2026", "reimbursable"]
+pub fn parse_tags(tags: &str) -> Vec<String> {
+    let mut parsed_tags = Vec::new();
+    for tag in tags
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(normalize_tag)
+    {
+        if !tag.is_empty() && !parsed_tags.contains(&tag) {
+            parsed_tags.push(tag);
+        }
+    }
+    parsed_tags
+}
+
+// one line of a split spending
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpendingSplit {
+    pub budget_category_id: BudgetCategoryId,
+    pub budget_category_name: BudgetCategory,
+    pub amount: MoneyAmount,
+    pub memo: String,
+}
+
+impl Spending {
+    pub fn is_reconciled(&self) -> bool {
+        self.status == ClearedStatus::Reconciled
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        self.tags.iter().any(|spending_tag| *spending_tag == tag)
+    }
+
+    pub fn tags_to_string(&self) -> String {
+        self.tags
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // what the split lines do not cover - should be 0 once the spending is fully split
+    pub fn unsplit_amount(&self) -> i32 {
+        self.splits
+            .iter()
+            .fold(self.amount.to_i32(), |total, split| {
+                total - split.amount.to_i32()
+            })
+    }
+
+    pub fn is_split_balanced(&self) -> bool {
+        self.splits.is_empty() || self.unsplit_amount() == 0
+    }
+
+    // how much goes to each category
+    // the part not covered by the splits of an unbalanced spending isn't booked anywhere until
+    // the split lines sum to the total, the view showing the spending as unbalanced meanwhile
+    pub fn amounts_per_category(&self) -> Vec<(BudgetCategoryId, i32)> {
+        if self.splits.is_empty() {
+            return vec![(self.budget_category_id, self.amount.to_i32())];
+        }
+        self.splits
+            .iter()
+            .map(|split| (split.budget_category_id, split.amount.to_i32()))
+            .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Spendings(pub Vec<Spending>);
 
+impl Spendings {
+    pub fn position(&self, id: SpendingId) -> Option<usize> {
+        self.0.iter().position(|spending| spending.id == id)
+    }
+}
+
+// Money moved from one envelope (i.e. budget category) to another during the month.
+// We keep every transfer instead of simply updating the budgets, so the history can be shown
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EnvelopeTransfer {
+    pub from: BudgetCategoryId,
+    pub to: BudgetCategoryId,
+    pub amount: BudgetAmount,
+    pub day: Day,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct MonthlyBudget {
     pub budgets: HashMap<BudgetCategoryId, BudgetAmount>,
     pub spendings: Spendings,
+    #[serde(default)]
+    pub envelope_transfers: Vec<EnvelopeTransfer>,
+    // keyed by the first day of the periods, only used when they aren't calendar months
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub period_budgets: BTreeMap<NaiveDate, PeriodBudget>,
 }
 
 impl Default for MonthlyBudget {
@@ -100,17 +498,61 @@ impl Default for MonthlyBudget {
         MonthlyBudget {
             budgets: HashMap::new(),
             spendings: Spendings(Vec::new()),
+            envelope_transfers: Vec::new(),
+            period_budgets: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+// the budgets of a period which isn't a calendar month; it's stored in the file of the month the
+// period starts in
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PeriodBudget {
+    pub budgets: HashMap<BudgetCategoryId, BudgetAmount>,
+    pub envelope_transfers: Vec<EnvelopeTransfer>,
+}
+
+impl MonthlyBudget {
+    // every credit recorded this month is considered as income
+    pub fn income(&self) -> i32 {
+        self.spendings
+            .0
+            .iter()
+            .map(|spending| spending.amount.to_i32())
+            .filter(|amount| *amount > 0)
+            .sum()
+    }
+
+    pub fn assigned(&self) -> i32 {
+        self.budgets.values().map(|amount| amount.0).sum()
+    }
+
+    // what is left in the pool after the budgets took their share
+    pub fn ready_to_assign(&self) -> i32 {
+        self.income() - self.assigned()
+    }
+
+    // how much was moved into (positive) or out of (negative) an envelope
+    pub fn net_transfers(&self, budget_category_id: BudgetCategoryId) -> i32 {
+        self.envelope_transfers.iter().fold(0, |total, transfer| {
+            if transfer.to == budget_category_id {
+                total + transfer.amount.0
+            } else if transfer.from == budget_category_id {
+                total - transfer.amount.0
+            } else {
+                total
+            }
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum MoneyAmountType {
     Credit,
     Debit,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MoneyAmount {
     amount_type: MoneyAmountType,
     whole: u32,
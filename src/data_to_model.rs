@@ -1,7 +1,23 @@
+use crate::alerts::Alert;
+use crate::bills::{BillOccurrence, BillRepeat, BillStatus, Bills, BILL_REPEATS};
+use crate::classifier::CategoryClassifier;
+use crate::config::BudgetingMode;
 use crate::data::*;
+use crate::duplicates::DuplicateOf;
+use crate::forecast::Forecast;
+use crate::goals::Goals;
+use crate::loans::{Loan, Loans};
+use crate::net_worth::{net_worth_over_time, HoldingKind, Valuations, HOLDING_KINDS};
+use crate::reports::{tag_totals, TaggedSpending};
+use crate::rules::Rules;
+use crate::shared_expenses::Settlement;
 use crate::translation_provider::TranslationProvider;
-use gtk::{GtkListStoreExtManual, StaticType, TreeModelExt};
+use gtk::{
+    Cast, GtkListStoreExt, GtkListStoreExtManual, ObjectExt, StaticType, TreeModelExt,
+    TreeModelFilterExt, TreeSortableExtManual, TreeStoreExtManual,
+};
 use log::debug;
+use std::collections::BTreeMap;
 
 pub const BACKGROUND_COLOR_NORMAL: &str = "#ffffff";
 pub const BACKGROUND_COLOR_IS_DEFAULT: &str = "#ddddee";
@@ -10,22 +26,8 @@ pub const BACKGROUND_COLOR_RENAMED_BUDGET_CATEGORY: &str = "#ddeedd";
 pub const BACKGROUND_COLOR_DEBIT: &str = "#f00";
 pub const BACKGROUND_COLOR_CREDIT: &str = "#0f0";
 pub const BACKGROUND_COLOR_NULL: &str = "#ff0";
-
-pub enum SpendingDayComboBoxIds {
-    Day = 0,
-}
-
-impl Into<i32> for SpendingDayComboBoxIds {
-    fn into(self) -> i32 {
-        self as i32
-    }
-}
-
-impl Into<u32> for SpendingDayComboBoxIds {
-    fn into(self) -> u32 {
-        self as u32
-    }
-}
+pub const BACKGROUND_COLOR_UNBALANCED_SPLIT: &str = "#eeddaa";
+pub const BACKGROUND_COLOR_DUPLICATE: &str = "#ddccee";
 
 pub enum BudgetCategoryComboBoxIds {
     Name = 0,
@@ -43,22 +45,26 @@ impl Into<u32> for BudgetCategoryComboBoxIds {
     }
 }
 
-pub enum BudgetCategoriesListStoreIds {
+pub enum BudgetCategoriesTreeStoreIds {
     Name = 0,
     Amount = 1,
     Balance = 2,
     NameBackgroundColor = 3,
     AmountBackgroundColor = 4,
     BalanceBackgroundColor = 5,
+    ParentName = 6,
+    ParentNameBackgroundColor = 7,
+    // hidden column, used to find the category of a row whatever its depth
+    Id = 8,
 }
 
-impl Into<i32> for BudgetCategoriesListStoreIds {
+impl Into<i32> for BudgetCategoriesTreeStoreIds {
     fn into(self) -> i32 {
         self as i32
     }
 }
 
-impl Into<u32> for BudgetCategoriesListStoreIds {
+impl Into<u32> for BudgetCategoriesTreeStoreIds {
     fn into(self) -> u32 {
         self as u32
     }
@@ -68,12 +74,40 @@ pub enum SpendingsGtkModelIds {
     Name = 0,
     CategoryName = 1,
     Amount = 2,
-    Day = 3,
+    Date = 3,
 
     NameBackgroundColor = 4,
     CategoryNameBackgroundColor = 5,
     AmountBackgroundColor = 6,
-    DayBackgroundColor = 7,
+    DateBackgroundColor = 7,
+
+    Tags = 8,
+    Note = 9,
+
+    // split lines don't have their own date, tags or note
+    SpendingCellsVisible = 10,
+
+    // learned category for the uncategorized spendings
+    Suggestion = 11,
+
+    Status = 12,
+    // reconciled spendings are locked
+    Editable = 13,
+
+    PaidBy = 14,
+    Share = 15,
+
+    Reimbursement = 16,
+
+    // hidden, to sort the amounts as numbers
+    AmountCents = 17,
+    // hidden, the default row stays last whichever the sort
+    IsDefault = 18,
+
+    // hidden, the rows are found back by the spending's id and not by their position
+    Id = 19,
+    // hidden, the split line's index in its spending
+    SplitIndex = 20,
 }
 
 impl Into<i32> for SpendingsGtkModelIds {
@@ -88,14 +122,57 @@ impl Into<u32> for SpendingsGtkModelIds {
     }
 }
 
+// id stored in the hidden column of rows not matching any budget category (i.e. the default one)
+pub const NO_BUDGET_CATEGORY_ID: u32 = std::u32::MAX;
+
+pub enum TagTotalsListStoreIds {
+    Tag = 0,
+    Total = 1,
+    TotalBackgroundColor = 2,
+}
+
+impl Into<i32> for TagTotalsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for TagTotalsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub enum TaggedSpendingsListStoreIds {
+    Date = 0,
+    Name = 1,
+    Tags = 2,
+    Amount = 3,
+    AmountBackgroundColor = 4,
+}
+
+impl Into<i32> for TaggedSpendingsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for TaggedSpendingsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
 pub fn get_model_from_budget_categories_and_monthly_budget(
     budget_categories: &BudgetCategories,
+    budget_category_parents: &BudgetCategoryParents,
     monthly_budget: &MonthlyBudget,
     translation_provider: &TranslationProvider,
-) -> gtk::ListStore {
-    use BudgetCategoriesListStoreIds::*;
+    budgeting_mode: BudgetingMode,
+) -> gtk::TreeStore {
+    use BudgetCategoriesTreeStoreIds::*;
 
-    let list = gtk::ListStore::new(&[
+    let tree = gtk::TreeStore::new(&[
         // name
         String::static_type(),
         // amount
@@ -108,39 +185,70 @@ pub fn get_model_from_budget_categories_and_monthly_budget(
         String::static_type(),
         // balance color
         String::static_type(),
+        // parent name
+        String::static_type(),
+        // parent name color
+        String::static_type(),
+        // id
+        u32::static_type(),
     ]);
 
     // collect for each budget_category how much was spent and all
     let mut balance_per_budget = std::collections::HashMap::new();
     for spending in &monthly_budget.spendings.0 {
-        *balance_per_budget
-            .entry(spending.budget_category_id)
-            .or_insert(0) += spending.amount.to_i32();
+        for (budget_category_id, amount) in spending.amounts_per_category() {
+            // with zero-based budgeting, income goes to the "ready to assign" pool instead
+            if budgeting_mode == BudgetingMode::ZeroBased && amount > 0 {
+                continue;
+            }
+            *balance_per_budget.entry(budget_category_id).or_insert(0) += amount;
+        }
+    }
+    for (budget_category_id, budget_category_amount) in &monthly_budget.budgets {
+        *balance_per_budget.entry(*budget_category_id).or_insert(0) += budget_category_amount.0;
+    }
+    for budget_category_id in budget_categories.0.keys() {
+        *balance_per_budget.entry(*budget_category_id).or_insert(0) +=
+            monthly_budget.net_transfers(*budget_category_id);
     }
 
-    for (budget_category_id, budget_category) in &budget_categories.0 {
+    // depth-first, so that the parents are always inserted before their children
+    let mut to_insert = budget_categories
+        .0
+        .keys()
+        .rev()
+        .filter(|id| match budget_category_parents.parent_of(**id) {
+            Some(parent) => !budget_categories.0.contains_key(&parent),
+            None => true,
+        })
+        .map(|id| (*id, None))
+        .collect::<Vec<(BudgetCategoryId, Option<gtk::TreeIter>)>>();
+    while let Some((budget_category_id, parent_iter)) = to_insert.pop() {
+        let budget_category = match budget_categories.0.get(&budget_category_id) {
+            Some(budget_category) => budget_category,
+            None => continue,
+        };
         let budget_category_amount = monthly_budget
             .budgets
             .get(&budget_category_id)
             .unwrap_or(&BudgetAmount(0));
-        let amount = MoneyAmount::from_i32(budget_category_amount.0);
-        let formatted_amount = translation_provider
-            .format_money(amount.sign(), amount.whole_with_separator(&translation_provider.thousands_separator()), amount.cents_padded())
-            .expect("Could not format the input in the budget_category_amount fn!");
-
-        let balance =
-            budget_category_amount.0 + balance_per_budget.get(&budget_category_id).unwrap_or(&0);
-        let balance_amount = MoneyAmount::from_i32(balance);
-        let formatted_balance = translation_provider
-            .format_money(
-                balance_amount.sign(),
-                balance_amount.whole_with_separator(&translation_provider.thousands_separator()),
-                balance_amount.cents_padded(),
-            )
-            .expect("Could not format the input in the budget_category_amount fn!");
+        let formatted_amount = format_amount(budget_category_amount.0, translation_provider);
+
+        let balance = rolled_up_balance(
+            budget_category_id,
+            budget_category_parents,
+            &balance_per_budget,
+        );
+        let formatted_balance = format_amount(balance, translation_provider);
         let balance_cell_color = amount_to_color(balance);
 
-        list.insert_with_values(
+        let parent_name = budget_category_parents
+            .parent_of(budget_category_id)
+            .and_then(|parent| budget_categories.0.get(&parent))
+            .map_or_else(String::new, |parent| parent.0.clone());
+
+        let iter = tree.insert_with_values(
+            parent_iter.as_ref(),
             None,
             &[
                 Name.into(),
@@ -149,6 +257,9 @@ pub fn get_model_from_budget_categories_and_monthly_budget(
                 NameBackgroundColor.into(),
                 AmountBackgroundColor.into(),
                 BalanceBackgroundColor.into(),
+                ParentName.into(),
+                ParentNameBackgroundColor.into(),
+                Id.into(),
             ],
             &[
                 &budget_category.0,
@@ -157,10 +268,38 @@ pub fn get_model_from_budget_categories_and_monthly_budget(
                 &BACKGROUND_COLOR_NORMAL,
                 &BACKGROUND_COLOR_NORMAL,
                 &balance_cell_color,
+                &parent_name,
+                &BACKGROUND_COLOR_NORMAL,
+                &budget_category_id.0,
             ],
         );
+
+        for child in budget_category_parents
+            .children_of(budget_category_id)
+            .into_iter()
+            .rev()
+        {
+            to_insert.push((child, Some(iter.clone())));
+        }
     }
-    list
+    tree
+}
+
+// own balance of the category, plus the ones of all its sub-categories
+fn rolled_up_balance(
+    budget_category_id: BudgetCategoryId,
+    budget_category_parents: &BudgetCategoryParents,
+    balance_per_budget: &std::collections::HashMap<BudgetCategoryId, i32>,
+) -> i32 {
+    budget_category_parents
+        .children_of(budget_category_id)
+        .into_iter()
+        .fold(
+            *balance_per_budget.get(&budget_category_id).unwrap_or(&0),
+            |total, child| {
+                total + rolled_up_balance(child, budget_category_parents, balance_per_budget)
+            },
+        )
 }
 
 impl Into<gtk::ListStore> for BudgetCategories {
@@ -180,21 +319,340 @@ impl Into<gtk::ListStore> for &BudgetCategories {
     }
 }
 
+// same as the budget categories' model, with an additional entry for "no category" - i.e. for
+// top-level categories, or payees without a default category
+pub fn get_optional_budget_category_model(
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use BudgetCategoryComboBoxIds::*;
+    let list: gtk::ListStore = budget_categories.into();
+    list.insert_with_values(
+        Some(0),
+        &[Name.into()],
+        &[&translation_provider.no_parent_budget_category()],
+    );
+    list
+}
+
+pub enum ReconcileListStoreIds {
+    Cleared = 0,
+    Date = 1,
+    Name = 2,
+    Amount = 3,
+    AmountBackgroundColor = 4,
+    // where the spending comes from, in the list of loaded months
+    MonthIndex = 5,
+    SpendingIndex = 6,
+}
+
+impl Into<i32> for ReconcileListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for ReconcileListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub enum PayeesListStoreIds {
+    Name = 0,
+    Aliases = 1,
+    DefaultBudgetCategoryName = 2,
+    NameBackgroundColor = 3,
+    // hidden column, the default row uses NO_PAYEE_ID
+    Id = 4,
+}
+
+impl Into<i32> for PayeesListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for PayeesListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub const NO_PAYEE_ID: u32 = std::u32::MAX;
+
+pub fn get_payees_model(
+    payees: &Payees,
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use PayeesListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // aliases
+        String::static_type(),
+        // default category
+        String::static_type(),
+        // name color
+        String::static_type(),
+        // id
+        u32::static_type(),
+    ]);
+    let columns = [
+        Name.into(),
+        Aliases.into(),
+        DefaultBudgetCategoryName.into(),
+        NameBackgroundColor.into(),
+        Id.into(),
+    ];
+    for (id, payee) in &payees.0 {
+        let default_budget_category_name = payee
+            .default_budget_category_id
+            .and_then(|budget_category_id| budget_categories.0.get(&budget_category_id))
+            .map_or_else(
+                || translation_provider.no_parent_budget_category(),
+                |budget_category| budget_category.0.clone(),
+            );
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &payee.name,
+                &payee.aliases_to_string(),
+                &default_budget_category_name,
+                &BACKGROUND_COLOR_NORMAL,
+                &id.0,
+            ],
+        );
+    }
+    list.insert_with_values(
+        None,
+        &columns,
+        &[
+            &translation_provider.payee_name_placeholder(),
+            &"",
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+            &NO_PAYEE_ID,
+        ],
+    );
+    list
+}
+
+// the completion's model is shared by every entry of the name column, so it is updated in place
+pub fn fill_payee_completion_model(model: &gtk::ListStore, payees: &Payees) {
+    model.clear();
+    for payee in payees.0.values() {
+        model.insert_with_values(None, &[0], &[&payee.name]);
+        for alias in &payee.aliases {
+            model.insert_with_values(None, &[0], &[alias]);
+        }
+    }
+}
+
+pub enum RulesListStoreIds {
+    Priority = 0,
+    NamePattern = 1,
+    MinAmount = 2,
+    MaxAmount = 3,
+    Day = 4,
+    CategoryName = 5,
+    BackgroundColor = 6,
+    NamePatternBackgroundColor = 7,
+}
+
+impl Into<i32> for RulesListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for RulesListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the rules are expected to be sorted by priority, so that the rows match their index
+pub fn get_rules_model(
+    rules: &Rules,
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use RulesListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // priority
+        String::static_type(),
+        // name pattern
+        String::static_type(),
+        // min amount
+        String::static_type(),
+        // max amount
+        String::static_type(),
+        // day
+        String::static_type(),
+        // category
+        String::static_type(),
+        // background
+        String::static_type(),
+        // name pattern background
+        String::static_type(),
+    ]);
+    let columns = [
+        Priority.into(),
+        NamePattern.into(),
+        MinAmount.into(),
+        MaxAmount.into(),
+        Day.into(),
+        CategoryName.into(),
+        BackgroundColor.into(),
+        NamePatternBackgroundColor.into(),
+    ];
+    let format_bound = |bound: Option<i32>| {
+        bound.map_or_else(String::new, |bound| {
+            format_amount(bound, translation_provider)
+        })
+    };
+    for rule in &rules.0 {
+        let budget_category_name = rule
+            .budget_category_id
+            .and_then(|budget_category_id| budget_categories.0.get(&budget_category_id))
+            .map_or_else(String::new, |budget_category| budget_category.0.clone());
+        let name_pattern_color = if rule.is_name_pattern_valid() {
+            BACKGROUND_COLOR_NORMAL
+        } else {
+            BACKGROUND_COLOR_WRONG_BUDGET_CATEGORY
+        };
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &rule.priority.to_string(),
+                &rule.name_pattern.clone().unwrap_or_default(),
+                &format_bound(rule.min_amount),
+                &format_bound(rule.max_amount),
+                &rule.day.map_or_else(String::new, |day| day.0.to_string()),
+                &budget_category_name,
+                &BACKGROUND_COLOR_NORMAL,
+                &name_pattern_color,
+            ],
+        );
+    }
+    // editing this one creates a new rule
+    list.insert_with_values(
+        None,
+        &columns,
+        &[
+            &"",
+            &"",
+            &"",
+            &"",
+            &"",
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+            &BACKGROUND_COLOR_IS_DEFAULT,
+        ],
+    );
+    list
+}
+
+pub enum GoalsListStoreIds {
+    Name = 0,
+    Target = 1,
+    Deadline = 2,
+    CategoryName = 3,
+    BackgroundColor = 4,
+}
+
+impl Into<i32> for GoalsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for GoalsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the rows match the goals' index, the last one is the default row
+pub fn get_goals_model(
+    goals: &Goals,
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use GoalsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // target
+        String::static_type(),
+        // deadline
+        String::static_type(),
+        // category
+        String::static_type(),
+        // background
+        String::static_type(),
+    ]);
+    let columns = [
+        Name.into(),
+        Target.into(),
+        Deadline.into(),
+        CategoryName.into(),
+        BackgroundColor.into(),
+    ];
+    for goal in &goals.0 {
+        let budget_category_name = goal
+            .budget_category_id
+            .and_then(|budget_category_id| budget_categories.0.get(&budget_category_id))
+            .map_or_else(String::new, |budget_category| budget_category.0.clone());
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &goal.name,
+                &format_amount(goal.target, translation_provider),
+                &goal.deadline.format(DATE_FORMAT).to_string(),
+                &budget_category_name,
+                &BACKGROUND_COLOR_NORMAL,
+            ],
+        );
+    }
+    // editing this one creates a new goal
+    list.insert_with_values(
+        None,
+        &columns,
+        &[
+            &translation_provider.goal_name_placeholder(),
+            &"",
+            &"",
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+        ],
+    );
+    list
+}
+
 pub fn get_spendings_model(
     monthly_budget: &MonthlyBudget,
     budget_categories: &BudgetCategories,
+    category_classifier: &CategoryClassifier,
+    duplicates: &[Option<DuplicateOf>],
+    members: &Members,
     translation_provider: &TranslationProvider,
-) -> gtk::ListStore {
+) -> gtk::TreeStore {
     use SpendingsGtkModelIds::*;
-    let spendings_list = gtk::ListStore::new(&[
+    let spendings_tree = gtk::TreeStore::new(&[
         // name
         String::static_type(),
         // category display string
         String::static_type(),
         // amount
         String::static_type(),
-        // day
-        i32::static_type(),
+        // date
+        String::static_type(),
         // background
         String::static_type(),
         // background
@@ -203,115 +661,1493 @@ pub fn get_spendings_model(
         String::static_type(),
         // background
         String::static_type(),
+        // tags
+        String::static_type(),
+        // note
+        String::static_type(),
+        // date, tags and note visible
+        bool::static_type(),
+        // suggestion
+        String::static_type(),
+        // status
+        String::static_type(),
+        // editable
+        bool::static_type(),
+        // paid by
+        String::static_type(),
+        // share
+        String::static_type(),
+        // reimbursement
+        String::static_type(),
+        // amount in cents
+        i32::static_type(),
+        // is default
+        bool::static_type(),
+        // id
+        u64::static_type(),
+        // split index
+        u32::static_type(),
     ]);
 
-    for spending in &monthly_budget.spendings.0 {
-        let (category_color, category_name) =
-            match budget_categories.0.get(&spending.budget_category_id) {
-                Some(name) => {
-                    if name.0 == spending.budget_category_name.0 {
-                        // Id and display string are the same - nothing special
-                        (
-                            BACKGROUND_COLOR_NORMAL,
-                            spending.budget_category_name.clone(),
-                        )
-                    } else {
-                        debug!(
-                            "Id exists but names re different. Old name: {}",
-                            spending.budget_category_name.0
-                        );
-                        debug!("New name: {}", name.0);
-                        // The id exists, but the category has been renamed - display the new one with a green background
-                        (BACKGROUND_COLOR_RENAMED_BUDGET_CATEGORY, name.clone())
-                    }
-                }
-                // Id doesn't exist - the category has been deleted
-                // We still show it, with a redbackground
-                None => (
-                    BACKGROUND_COLOR_WRONG_BUDGET_CATEGORY,
-                    spending.budget_category_name.clone(),
-                ),
-            };
-
-        let amount_cell_background_color = amount_to_color(spending.amount.to_i32());
-        let formatted_amount = translation_provider
-            .format_money(
-                spending.amount.sign(),
-                spending.amount.whole_with_separator(&translation_provider.thousands_separator()),
-                spending.amount.cents_padded(),
-            )
-            .expect("Could not format the input in the budget_category_amount fn!");
-
-        spendings_list.insert_with_values(
+    for (spending_idx, spending) in monthly_budget.spendings.0.iter().enumerate() {
+        let (category_color, category_name) = category_color_and_name(
+            spending.budget_category_id,
+            &spending.budget_category_name,
+            budget_categories,
+        );
+
+        // the amount's color is replaced to show the split lines do not add up
+        let amount_cell_background_color = if spending.is_split_balanced() {
+            amount_to_color(spending.amount.to_i32())
+        } else {
+            BACKGROUND_COLOR_UNBALANCED_SPLIT
+        };
+        let formatted_amount = format_amount(spending.amount.to_i32(), translation_provider);
+        // the name and day are what tell the duplicates apart from the legit spendings
+        let duplicate_background_color = if duplicates
+            .get(spending_idx)
+            .map_or(false, |duplicate| duplicate.is_some())
+        {
+            BACKGROUND_COLOR_DUPLICATE
+        } else {
+            BACKGROUND_COLOR_NORMAL
+        };
+        let suggestion = if spending.budget_category_id == BudgetCategoryId(NO_BUDGET_CATEGORY_ID) {
+            category_classifier
+                .suggest(spending)
+                .and_then(|(id, confidence)| {
+                    budget_categories.0.get(&id).map(|name| {
+                        translation_provider
+                            .category_suggestion(
+                                name.0.clone(),
+                                format!("{:.0}", confidence * 100.0),
+                            )
+                            .expect("Could not format the category suggestion")
+                    })
+                })
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        let status = match spending.status {
+            ClearedStatus::Uncleared => String::new(),
+            ClearedStatus::Cleared => translation_provider.status_cleared(),
+            ClearedStatus::Reconciled => translation_provider.status_reconciled(),
+        };
+        let editable = !spending.is_reconciled();
+        // the share only matters for the shared spendings
+        let (paid_by, share) = match spending
+            .paid_by
+            .and_then(|member_id| members.0.get(&member_id))
+        {
+            Some(member) => (
+                member.name.clone(),
+                share_rule_to_string(&spending.share_rule, members, translation_provider),
+            ),
+            None => (String::new(), String::new()),
+        };
+        let reimbursement = spending.reimbursement.map_or_else(String::new, |status| {
+            reimbursement_status_name(status, translation_provider)
+        });
+
+        let spending_iter = spendings_tree.insert_with_values(
+            None,
             None,
             &[
                 Name.into(),
                 CategoryName.into(),
                 Amount.into(),
-                Day.into(),
+                Date.into(),
                 NameBackgroundColor.into(),
                 CategoryNameBackgroundColor.into(),
                 AmountBackgroundColor.into(),
-                DayBackgroundColor.into(),
+                DateBackgroundColor.into(),
+                Tags.into(),
+                Note.into(),
+                SpendingCellsVisible.into(),
+                Suggestion.into(),
+                Status.into(),
+                Editable.into(),
+                PaidBy.into(),
+                Share.into(),
+                Reimbursement.into(),
+                AmountCents.into(),
+                IsDefault.into(),
+                Id.into(),
             ],
             &[
                 &spending.name,
                 &category_name.0,
                 &formatted_amount,
-                &spending.day.0,
-                &BACKGROUND_COLOR_NORMAL,
+                &spending.date.format(DATE_FORMAT).to_string(),
+                &duplicate_background_color,
                 &category_color,
                 &amount_cell_background_color,
-                &BACKGROUND_COLOR_NORMAL,
+                &duplicate_background_color,
+                &spending.tags_to_string(),
+                &spending.note,
+                &true,
+                &suggestion,
+                &status,
+                &editable,
+                &paid_by,
+                &share,
+                &reimbursement,
+                &spending.amount.to_i32(),
+                &false,
+                &spending.id.0,
             ],
         );
+
+        for (split_idx, split) in spending.splits.iter().enumerate() {
+            let (category_color, category_name) = category_color_and_name(
+                split.budget_category_id,
+                &split.budget_category_name,
+                budget_categories,
+            );
+            let formatted_amount = format_amount(split.amount.to_i32(), translation_provider);
+            spendings_tree.insert_with_values(
+                Some(&spending_iter),
+                None,
+                &[
+                    Name.into(),
+                    CategoryName.into(),
+                    Amount.into(),
+                    Date.into(),
+                    NameBackgroundColor.into(),
+                    CategoryNameBackgroundColor.into(),
+                    AmountBackgroundColor.into(),
+                    DateBackgroundColor.into(),
+                    Tags.into(),
+                    Note.into(),
+                    SpendingCellsVisible.into(),
+                    Editable.into(),
+                    AmountCents.into(),
+                    IsDefault.into(),
+                    Id.into(),
+                    SplitIndex.into(),
+                ],
+                &[
+                    &split.memo,
+                    &category_name.0,
+                    &formatted_amount,
+                    &spending.date.format(DATE_FORMAT).to_string(),
+                    &BACKGROUND_COLOR_NORMAL,
+                    &category_color,
+                    &amount_to_color(split.amount.to_i32()),
+                    &BACKGROUND_COLOR_NORMAL,
+                    &"",
+                    &"",
+                    &false,
+                    &editable,
+                    &split.amount.to_i32(),
+                    &false,
+                    &spending.id.0,
+                    &(split_idx as u32),
+                ],
+            );
+        }
     }
-    order_spendings_by_day(&spendings_list);
-    spendings_list
+    spendings_tree
 }
 
-pub fn list_model_from_month_year(m: Month, y: Year) -> gtk::ListStore {
-    use SpendingDayComboBoxIds::Day;
-    let list = gtk::ListStore::new(&[String::static_type()]);
-
-    let max_day = match m {
-        Month::Jan
-        | Month::Mar
-        | Month::May
-        | Month::Jul
-        | Month::Aug
-        | Month::Oct
-        | Month::Dec => 31,
-        Month::Apr | Month::Jun | Month::Sep | Month::Nov => 30,
-
-        Month::Feb => {
-            if (y.0 % 4 == 0 && y.0 % 100 != 0) || y.0 % 400 == 0 {
-                29
+// the name displayed for a spending's (or split line's) category, and the cell's color
+fn category_color_and_name(
+    budget_category_id: BudgetCategoryId,
+    budget_category_name: &BudgetCategory,
+    budget_categories: &BudgetCategories,
+) -> (&'static str, BudgetCategory) {
+    match budget_categories.0.get(&budget_category_id) {
+        Some(name) => {
+            if name.0 == budget_category_name.0 {
+                // Id and display string are the same - nothing special
+                (BACKGROUND_COLOR_NORMAL, budget_category_name.clone())
             } else {
-                28
+                debug!(
+                    "Id exists but names re different. Old name: {}",
+                    budget_category_name.0
+                );
+                debug!("New name: {}", name.0);
+                // The id exists, but the category has been renamed - display the new one with a green background
+                (BACKGROUND_COLOR_RENAMED_BUDGET_CATEGORY, name.clone())
             }
         }
-    };
-
-    for i in 1..=max_day {
-        list.insert_with_values(None, &[Day.into()], &[&i.to_string()]);
+        // Id doesn't exist - the category has been deleted
+        // We still show it, with a redbackground
+        None => (
+            BACKGROUND_COLOR_WRONG_BUDGET_CATEGORY,
+            budget_category_name.clone(),
+        ),
     }
-    list
 }
 
-pub fn add_default_spending(model: &gtk::ListStore, today: Day) {
-    use SpendingsGtkModelIds::*;
-    model.insert_with_values(
-        None,
-        &[
+pub fn get_tag_totals_model(
+    tagged_spendings: &[TaggedSpending],
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use TagTotalsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // tag
+        String::static_type(),
+        // total
+        String::static_type(),
+        // total color
+        String::static_type(),
+    ]);
+    for (tag, total) in tag_totals(tagged_spendings) {
+        list.insert_with_values(
+            None,
+            &[Tag.into(), Total.into(), TotalBackgroundColor.into()],
+            &[
+                &format!("#{}", tag),
+                &format_amount(total, translation_provider),
+                &amount_to_color(total),
+            ],
+        );
+    }
+    list
+}
+
+pub enum ForecastListStoreIds {
+    MonthName = 0,
+    Income = 1,
+    Expenses = 2,
+    Balance = 3,
+    BalanceBackgroundColor = 4,
+}
+
+impl Into<i32> for ForecastListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for ForecastListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_forecast_model(
+    forecast: &Forecast,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use ForecastListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // month
+        String::static_type(),
+        // income
+        String::static_type(),
+        // expenses
+        String::static_type(),
+        // balance
+        String::static_type(),
+        // balance color
+        String::static_type(),
+    ]);
+    for forecast_month in &forecast.months {
+        list.insert_with_values(
+            None,
+            &[
+                MonthName.into(),
+                Income.into(),
+                Expenses.into(),
+                Balance.into(),
+                BalanceBackgroundColor.into(),
+            ],
+            &[
+                &date_in_month(forecast_month.month, forecast_month.year, 1)
+                    .format("%Y-%m")
+                    .to_string(),
+                &format_amount(forecast_month.income, translation_provider),
+                &format_amount(forecast_month.expenses, translation_provider),
+                &format_amount(forecast_month.balance, translation_provider),
+                &amount_to_color(forecast_month.balance),
+            ],
+        );
+    }
+    list
+}
+
+pub fn holding_kind_name(kind: HoldingKind, translation_provider: &TranslationProvider) -> String {
+    match kind {
+        HoldingKind::Account => translation_provider.holding_kind_account(),
+        HoldingKind::Property => translation_provider.holding_kind_property(),
+        HoldingKind::Investment => translation_provider.holding_kind_investment(),
+        HoldingKind::Loan => translation_provider.holding_kind_loan(),
+        HoldingKind::OtherLiability => translation_provider.holding_kind_other_liability(),
+    }
+}
+
+// same layout as the budget categories' combo boxes
+pub fn get_holding_kinds_model(translation_provider: &TranslationProvider) -> gtk::ListStore {
+    use BudgetCategoryComboBoxIds::*;
+    let list = gtk::ListStore::new(&[String::static_type()]);
+    for kind in HOLDING_KINDS.iter() {
+        list.insert_with_values(
+            None,
+            &[Name.into()],
+            &[&holding_kind_name(*kind, translation_provider)],
+        );
+    }
+    list
+}
+
+pub fn bill_repeat_name(repeat: BillRepeat, translation_provider: &TranslationProvider) -> String {
+    match repeat {
+        BillRepeat::Once => translation_provider.bill_repeat_once(),
+        BillRepeat::Monthly => translation_provider.bill_repeat_monthly(),
+        BillRepeat::Yearly => translation_provider.bill_repeat_yearly(),
+    }
+}
+
+// same layout as the budget categories' combo boxes
+pub fn get_bill_repeats_model(translation_provider: &TranslationProvider) -> gtk::ListStore {
+    use BudgetCategoryComboBoxIds::*;
+    let list = gtk::ListStore::new(&[String::static_type()]);
+    for repeat in BILL_REPEATS.iter() {
+        list.insert_with_values(
+            None,
+            &[Name.into()],
+            &[&bill_repeat_name(*repeat, translation_provider)],
+        );
+    }
+    list
+}
+
+pub enum BillsListStoreIds {
+    Name = 0,
+    Amount = 1,
+    DueDate = 2,
+    Repeat = 3,
+    CategoryName = 4,
+    BackgroundColor = 5,
+}
+
+impl Into<i32> for BillsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for BillsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_bills_model(
+    bills: &Bills,
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use BillsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // amount
+        String::static_type(),
+        // due date
+        String::static_type(),
+        // repeat
+        String::static_type(),
+        // category
+        String::static_type(),
+        // background
+        String::static_type(),
+    ]);
+    let columns = [
+        Name.into(),
+        Amount.into(),
+        DueDate.into(),
+        Repeat.into(),
+        CategoryName.into(),
+        BackgroundColor.into(),
+    ];
+    for bill in &bills.0 {
+        let budget_category_name = bill
+            .budget_category_id
+            .and_then(|budget_category_id| budget_categories.0.get(&budget_category_id))
+            .map_or_else(String::new, |budget_category| budget_category.0.clone());
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &bill.name,
+                &format_amount(bill.amount, translation_provider),
+                &bill.due_date.format(DATE_FORMAT).to_string(),
+                &bill_repeat_name(bill.repeat, translation_provider),
+                &budget_category_name,
+                &BACKGROUND_COLOR_NORMAL,
+            ],
+        );
+    }
+    // editing this one creates a new bill
+    list.insert_with_values(
+        None,
+        &columns,
+        &[
+            &translation_provider.bill_name_placeholder(),
+            &"",
+            &"",
+            &"",
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+        ],
+    );
+    list
+}
+
+pub enum BillOccurrencesListStoreIds {
+    DueDate = 0,
+    Name = 1,
+    Amount = 2,
+    Status = 3,
+    StatusBackgroundColor = 4,
+    BillIndex = 5,
+}
+
+impl Into<i32> for BillOccurrencesListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for BillOccurrencesListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_bill_occurrences_model(
+    occurrences: &[BillOccurrence],
+    bills: &Bills,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use BillOccurrencesListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // due date
+        String::static_type(),
+        // name
+        String::static_type(),
+        // amount
+        String::static_type(),
+        // status
+        String::static_type(),
+        // status color
+        String::static_type(),
+        // bill index
+        u32::static_type(),
+    ]);
+    for occurrence in occurrences {
+        let bill = &bills.0[occurrence.bill_idx];
+        let (status, status_color) = match occurrence.status {
+            BillStatus::Paid => (
+                translation_provider.bill_status_paid(),
+                BACKGROUND_COLOR_NORMAL,
+            ),
+            BillStatus::Due => (
+                translation_provider.bill_status_due(),
+                BACKGROUND_COLOR_IS_DEFAULT,
+            ),
+            BillStatus::Overdue => (
+                translation_provider.bill_status_overdue(),
+                BACKGROUND_COLOR_WRONG_BUDGET_CATEGORY,
+            ),
+        };
+        list.insert_with_values(
+            None,
+            &[
+                DueDate.into(),
+                Name.into(),
+                Amount.into(),
+                Status.into(),
+                StatusBackgroundColor.into(),
+                BillIndex.into(),
+            ],
+            &[
+                &occurrence.due_date.format(DATE_FORMAT).to_string(),
+                &bill.name,
+                &format_amount(bill.amount, translation_provider),
+                &status,
+                &status_color,
+                &(occurrence.bill_idx as u32),
+            ],
+        );
+    }
+    list
+}
+
+pub fn get_holding_names_model(valuations: &Valuations) -> gtk::ListStore {
+    use BudgetCategoryComboBoxIds::*;
+    let list = gtk::ListStore::new(&[String::static_type()]);
+    for holding in valuations.holdings.values() {
+        list.insert_with_values(None, &[Name.into()], &[&holding.name]);
+    }
+    list
+}
+
+pub enum HoldingsListStoreIds {
+    Name = 0,
+    Kind = 1,
+    BackgroundColor = 2,
+}
+
+impl Into<i32> for HoldingsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for HoldingsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the rows are in the order of the ids, the last one is the default row
+pub fn get_holdings_model(
+    valuations: &Valuations,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use HoldingsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // kind
+        String::static_type(),
+        // background
+        String::static_type(),
+    ]);
+    let columns = [Name.into(), Kind.into(), BackgroundColor.into()];
+    for holding in valuations.holdings.values() {
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &holding.name,
+                &holding_kind_name(holding.kind, translation_provider),
+                &BACKGROUND_COLOR_NORMAL,
+            ],
+        );
+    }
+    // editing this one creates a new holding
+    list.insert_with_values(
+        None,
+        &columns,
+        &[
+            &translation_provider.holding_name_placeholder(),
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+        ],
+    );
+    list
+}
+
+pub enum ValuationsListStoreIds {
+    Date = 0,
+    HoldingName = 1,
+    Value = 2,
+    BackgroundColor = 3,
+}
+
+impl Into<i32> for ValuationsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for ValuationsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the valuations are expected to be sorted by date, so that the rows match their index
+pub fn get_valuations_model(
+    valuations: &Valuations,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use ValuationsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // date
+        String::static_type(),
+        // holding
+        String::static_type(),
+        // value
+        String::static_type(),
+        // background
+        String::static_type(),
+    ]);
+    let columns = [
+        Date.into(),
+        HoldingName.into(),
+        Value.into(),
+        BackgroundColor.into(),
+    ];
+    for valuation in &valuations.valuations {
+        let (holding_name, color) = match valuations.holdings.get(&valuation.holding_id) {
+            Some(holding) => (holding.name.clone(), BACKGROUND_COLOR_NORMAL),
+            None => (String::new(), BACKGROUND_COLOR_WRONG_BUDGET_CATEGORY),
+        };
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &valuation.date.format(DATE_FORMAT).to_string(),
+                &holding_name,
+                &format_amount(valuation.value, translation_provider),
+                &color,
+            ],
+        );
+    }
+    // editing this one creates a new valuation
+    list.insert_with_values(
+        None,
+        &columns,
+        &[&"", &"", &"", &BACKGROUND_COLOR_IS_DEFAULT],
+    );
+    list
+}
+
+pub enum NetWorthListStoreIds {
+    Date = 0,
+    Assets = 1,
+    Liabilities = 2,
+    NetWorth = 3,
+    NetWorthBackgroundColor = 4,
+}
+
+impl Into<i32> for NetWorthListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for NetWorthListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_net_worth_model(
+    valuations: &Valuations,
+    today: chrono::NaiveDate,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use NetWorthListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // date
+        String::static_type(),
+        // assets
+        String::static_type(),
+        // liabilities
+        String::static_type(),
+        // net worth
+        String::static_type(),
+        // net worth color
+        String::static_type(),
+    ]);
+    for point in net_worth_over_time(valuations, today) {
+        list.insert_with_values(
+            None,
+            &[
+                Date.into(),
+                Assets.into(),
+                Liabilities.into(),
+                NetWorth.into(),
+                NetWorthBackgroundColor.into(),
+            ],
+            &[
+                &point.date.format(DATE_FORMAT).to_string(),
+                &format_amount(point.assets, translation_provider),
+                &format_amount(point.liabilities, translation_provider),
+                &format_amount(point.net_worth(), translation_provider),
+                &amount_to_color(point.net_worth()),
+            ],
+        );
+    }
+    list
+}
+
+pub enum LoansListStoreIds {
+    Name = 0,
+    Principal = 1,
+    Rate = 2,
+    Term = 3,
+    Start = 4,
+    PaymentDay = 5,
+    CategoryName = 6,
+    MonthlyPayment = 7,
+    BackgroundColor = 8,
+}
+
+impl Into<i32> for LoansListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for LoansListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the rows match the loans' index, the last one is the default row
+pub fn get_loans_model(
+    loans: &Loans,
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use LoansListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // principal
+        String::static_type(),
+        // rate
+        String::static_type(),
+        // term
+        String::static_type(),
+        // start
+        String::static_type(),
+        // payment day
+        String::static_type(),
+        // category
+        String::static_type(),
+        // monthly payment
+        String::static_type(),
+        // background
+        String::static_type(),
+    ]);
+    let columns = [
+        Name.into(),
+        Principal.into(),
+        Rate.into(),
+        Term.into(),
+        Start.into(),
+        PaymentDay.into(),
+        CategoryName.into(),
+        MonthlyPayment.into(),
+        BackgroundColor.into(),
+    ];
+    for loan in &loans.0 {
+        let budget_category_name = loan
+            .budget_category_id
+            .and_then(|budget_category_id| budget_categories.0.get(&budget_category_id))
+            .map_or_else(String::new, |budget_category| budget_category.0.clone());
+        list.insert_with_values(
+            None,
+            &columns,
+            &[
+                &loan.name,
+                &format_amount(loan.principal, translation_provider),
+                &loan.annual_rate.to_string(),
+                &loan.term_months.to_string(),
+                &loan.start.format(DATE_FORMAT).to_string(),
+                &loan.payment_day.to_string(),
+                &budget_category_name,
+                &format_amount(loan.monthly_payment(), translation_provider),
+                &BACKGROUND_COLOR_NORMAL,
+            ],
+        );
+    }
+    // editing this one creates a new loan
+    list.insert_with_values(
+        None,
+        &columns,
+        &[
+            &translation_provider.loan_name_placeholder(),
+            &"",
+            &"",
+            &"",
+            &"",
+            &"",
+            &"",
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+        ],
+    );
+    list
+}
+
+pub enum LoanScheduleListStoreIds {
+    Number = 0,
+    Date = 1,
+    Payment = 2,
+    Interest = 3,
+    Principal = 4,
+    Extra = 5,
+    Remaining = 6,
+}
+
+impl Into<i32> for LoanScheduleListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for LoanScheduleListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_loan_schedule_model(
+    loan: &Loan,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use LoanScheduleListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // number
+        String::static_type(),
+        // date
+        String::static_type(),
+        // payment
+        String::static_type(),
+        // interest
+        String::static_type(),
+        // principal
+        String::static_type(),
+        // extra
+        String::static_type(),
+        // remaining
+        String::static_type(),
+    ]);
+    for payment in loan.schedule() {
+        let extra = if payment.extra == 0 {
+            String::new()
+        } else {
+            format_amount(payment.extra, translation_provider)
+        };
+        list.insert_with_values(
+            None,
+            &[
+                Number.into(),
+                Date.into(),
+                Payment.into(),
+                Interest.into(),
+                Principal.into(),
+                Extra.into(),
+                Remaining.into(),
+            ],
+            &[
+                &payment.number.to_string(),
+                &payment.date.format(DATE_FORMAT).to_string(),
+                &format_amount(payment.payment, translation_provider),
+                &format_amount(payment.interest, translation_provider),
+                &format_amount(payment.principal, translation_provider),
+                &extra,
+                &format_amount(payment.remaining, translation_provider),
+            ],
+        );
+    }
+    list
+}
+
+pub enum MembersListStoreIds {
+    Name = 0,
+    BackgroundColor = 1,
+}
+
+impl Into<i32> for MembersListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for MembersListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the rows are in the order of the ids, the last one is the default row
+pub fn get_members_model(
+    members: &Members,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use MembersListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // background
+        String::static_type(),
+    ]);
+    for member in members.0.values() {
+        list.insert_with_values(
+            None,
+            &[Name.into(), BackgroundColor.into()],
+            &[&member.name, &BACKGROUND_COLOR_NORMAL],
+        );
+    }
+    // editing this one creates a new member
+    list.insert_with_values(
+        None,
+        &[Name.into(), BackgroundColor.into()],
+        &[
+            &translation_provider.member_name_placeholder(),
+            &BACKGROUND_COLOR_IS_DEFAULT,
+        ],
+    );
+    list
+}
+
+// same layout as the budget categories' combo boxes, "nobody" being the first entry
+pub fn get_optional_member_model(
+    members: &Members,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use BudgetCategoryComboBoxIds::*;
+    let list = gtk::ListStore::new(&[String::static_type()]);
+    list.insert_with_values(None, &[Name.into()], &[&translation_provider.no_member()]);
+    for member in members.0.values() {
+        list.insert_with_values(None, &[Name.into()], &[&member.name]);
+    }
+    list
+}
+
+fn member_name(members: &Members, member_id: MemberId) -> String {
+    members
+        .0
+        .get(&member_id)
+        .map_or_else(String::new, |member| member.name.clone())
+}
+
+// the opposite of shared_expenses::parse_share_rule
+pub fn share_rule_to_string(
+    share_rule: &ShareRule,
+    members: &Members,
+    translation_provider: &TranslationProvider,
+) -> String {
+    match share_rule {
+        ShareRule::Equal => translation_provider.share_equal(),
+        ShareRule::Percentages(percentages) => percentages
+            .iter()
+            .map(|(member_id, percentage)| {
+                format!("{} {}%", member_name(members, *member_id), percentage)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        ShareRule::ExactAmounts(exact_amounts) => exact_amounts
+            .iter()
+            .map(|(member_id, amount)| {
+                let amount = MoneyAmount::from_i32(*amount);
+                format!(
+                    "{} {}{}{}{}",
+                    member_name(members, *member_id),
+                    amount.sign(),
+                    amount.whole_with_separator(""),
+                    translation_provider.decimal_separator(),
+                    amount.cents_padded()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+pub enum MemberBalancesListStoreIds {
+    Name = 0,
+    Balance = 1,
+    BalanceBackgroundColor = 2,
+}
+
+impl Into<i32> for MemberBalancesListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for MemberBalancesListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_member_balances_model(
+    balances: &BTreeMap<MemberId, i32>,
+    members: &Members,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use MemberBalancesListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // name
+        String::static_type(),
+        // balance
+        String::static_type(),
+        // balance color
+        String::static_type(),
+    ]);
+    for (member_id, balance) in balances {
+        list.insert_with_values(
+            None,
+            &[Name.into(), Balance.into(), BalanceBackgroundColor.into()],
+            &[
+                &member_name(members, *member_id),
+                &format_amount(*balance, translation_provider),
+                &amount_to_color(*balance),
+            ],
+        );
+    }
+    list
+}
+
+pub enum SettlementsListStoreIds {
+    Transfer = 0,
+}
+
+impl Into<i32> for SettlementsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for SettlementsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_settlements_model(
+    settlements: &[Settlement],
+    members: &Members,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use SettlementsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // transfer
+        String::static_type(),
+    ]);
+    for settlement in settlements {
+        list.insert_with_values(
+            None,
+            &[Transfer.into()],
+            &[&translation_provider
+                .settle_up_transfer(
+                    member_name(members, settlement.from),
+                    member_name(members, settlement.to),
+                    format_amount(settlement.amount, translation_provider),
+                )
+                .expect("Could not format the transfer!")],
+        );
+    }
+    list
+}
+
+pub fn get_tagged_spendings_model(
+    tagged_spendings: &[TaggedSpending],
+    tag: &str,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use TaggedSpendingsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // date
+        String::static_type(),
+        // name
+        String::static_type(),
+        // tags
+        String::static_type(),
+        // amount
+        String::static_type(),
+        // amount color
+        String::static_type(),
+    ]);
+    for tagged_spending in tagged_spendings
+        .iter()
+        .filter(|tagged_spending| tagged_spending.has_tag(tag))
+    {
+        let tags = tagged_spending
+            .tags
+            .iter()
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" ");
+        list.insert_with_values(
+            None,
+            &[
+                Date.into(),
+                Name.into(),
+                Tags.into(),
+                Amount.into(),
+                AmountBackgroundColor.into(),
+            ],
+            &[
+                &tagged_spending.date_to_string(),
+                &tagged_spending.name,
+                &tags,
+                &format_amount(tagged_spending.amount, translation_provider),
+                &amount_to_color(tagged_spending.amount),
+            ],
+        );
+    }
+    list
+}
+
+pub enum SearchResultsListStoreIds {
+    MonthName = 0,
+    Date = 1,
+    Name = 2,
+    CategoryName = 3,
+    Amount = 4,
+    AmountBackgroundColor = 5,
+    MonthIndex = 6,
+    SpendingIndex = 7,
+}
+
+impl Into<i32> for SearchResultsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for SearchResultsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_search_results_model(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    results: &[(usize, usize)],
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use SearchResultsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // month
+        String::static_type(),
+        // date
+        String::static_type(),
+        // name
+        String::static_type(),
+        // category
+        String::static_type(),
+        // amount
+        String::static_type(),
+        // amount color
+        String::static_type(),
+        // month index
+        u32::static_type(),
+        // spending index
+        u32::static_type(),
+    ]);
+    for (month_idx, spending_idx) in results {
+        let (m, y, monthly_budget) = &monthly_budgets[*month_idx];
+        let spending = &monthly_budget.spendings.0[*spending_idx];
+        list.insert_with_values(
+            None,
+            &[
+                MonthName.into(),
+                Date.into(),
+                Name.into(),
+                CategoryName.into(),
+                Amount.into(),
+                AmountBackgroundColor.into(),
+                MonthIndex.into(),
+                SpendingIndex.into(),
+            ],
+            &[
+                &date_in_month(*m, *y, 1).format("%Y-%m").to_string(),
+                &spending.date.format(DATE_FORMAT).to_string(),
+                &spending.name,
+                &spending.budget_category_name.0,
+                &format_amount(spending.amount.to_i32(), translation_provider),
+                &amount_to_color(spending.amount.to_i32()),
+                &(*month_idx as u32),
+                &(*spending_idx as u32),
+            ],
+        );
+    }
+    list
+}
+
+// the spendings which can still be ticked off a statement ending at the given date
+pub fn get_reconcile_model(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    end: chrono::NaiveDate,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use ReconcileListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // cleared
+        bool::static_type(),
+        // date
+        String::static_type(),
+        // name
+        String::static_type(),
+        // amount
+        String::static_type(),
+        // amount color
+        String::static_type(),
+        // month index
+        u32::static_type(),
+        // spending index
+        u32::static_type(),
+    ]);
+    for (month_idx, (_, _, monthly_budget)) in monthly_budgets.iter().enumerate() {
+        for (spending_idx, spending) in monthly_budget.spendings.0.iter().enumerate() {
+            if spending.date > end || spending.is_reconciled() {
+                continue;
+            }
+            list.insert_with_values(
+                None,
+                &[
+                    Cleared.into(),
+                    Date.into(),
+                    Name.into(),
+                    Amount.into(),
+                    AmountBackgroundColor.into(),
+                    MonthIndex.into(),
+                    SpendingIndex.into(),
+                ],
+                &[
+                    &(spending.status == ClearedStatus::Cleared),
+                    &spending.date.format(DATE_FORMAT).to_string(),
+                    &spending.name,
+                    &format_amount(spending.amount.to_i32(), translation_provider),
+                    &amount_to_color(spending.amount.to_i32()),
+                    &(month_idx as u32),
+                    &(spending_idx as u32),
+                ],
+            );
+        }
+    }
+    list
+}
+
+pub fn reimbursement_status_name(
+    status: ReimbursementStatus,
+    translation_provider: &TranslationProvider,
+) -> String {
+    match status {
+        ReimbursementStatus::Pending => translation_provider.reimbursement_pending(),
+        ReimbursementStatus::Submitted => translation_provider.reimbursement_submitted(),
+        ReimbursementStatus::Reimbursed => translation_provider.reimbursement_reimbursed(),
+    }
+}
+
+// same layout as the budget categories' combo boxes, "not reimbursable" being the first entry
+pub fn get_reimbursement_statuses_model(
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use BudgetCategoryComboBoxIds::*;
+    let list = gtk::ListStore::new(&[String::static_type()]);
+    list.insert_with_values(
+        None,
+        &[Name.into()],
+        &[&translation_provider.not_reimbursable()],
+    );
+    for status in REIMBURSEMENT_STATUSES.iter() {
+        list.insert_with_values(
+            None,
+            &[Name.into()],
+            &[&reimbursement_status_name(*status, translation_provider)],
+        );
+    }
+    list
+}
+
+pub enum ReimbursementsListStoreIds {
+    Date = 0,
+    Name = 1,
+    CategoryName = 2,
+    Amount = 3,
+    AmountBackgroundColor = 4,
+    Status = 5,
+    // where the spending comes from, in the list of loaded months
+    MonthIndex = 6,
+    SpendingIndex = 7,
+}
+
+impl Into<i32> for ReimbursementsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for ReimbursementsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+// the spendings of every month matching the filter, i.e. the outstanding expenses or the incomes
+// which may pay them back
+pub fn get_reimbursements_model<F>(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    filter: F,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore
+where
+    F: Fn(&Spending) -> bool,
+{
+    use ReimbursementsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // date
+        String::static_type(),
+        // name
+        String::static_type(),
+        // category
+        String::static_type(),
+        // amount
+        String::static_type(),
+        // amount color
+        String::static_type(),
+        // status
+        String::static_type(),
+        // month index
+        u32::static_type(),
+        // spending index
+        u32::static_type(),
+    ]);
+    for (month_idx, (_, _, monthly_budget)) in monthly_budgets.iter().enumerate() {
+        for (spending_idx, spending) in monthly_budget.spendings.0.iter().enumerate() {
+            if !filter(spending) {
+                continue;
+            }
+            let status = spending.reimbursement.map_or_else(String::new, |status| {
+                reimbursement_status_name(status, translation_provider)
+            });
+            list.insert_with_values(
+                None,
+                &[
+                    Date.into(),
+                    Name.into(),
+                    CategoryName.into(),
+                    Amount.into(),
+                    AmountBackgroundColor.into(),
+                    Status.into(),
+                    MonthIndex.into(),
+                    SpendingIndex.into(),
+                ],
+                &[
+                    &spending.date.format(DATE_FORMAT).to_string(),
+                    &spending.name,
+                    &spending.budget_category_name.0,
+                    &format_amount(spending.amount.to_i32(), translation_provider),
+                    &amount_to_color(spending.amount.to_i32()),
+                    &status,
+                    &(month_idx as u32),
+                    &(spending_idx as u32),
+                ],
+            );
+        }
+    }
+    list
+}
+
+pub fn alert_message(
+    alert: &Alert,
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> String {
+    let category_name = |budget_category_id| {
+        budget_categories
+            .0
+            .get(budget_category_id)
+            .map_or_else(String::new, |budget_category| budget_category.0.clone())
+    };
+    match alert {
+        Alert::NearlySpent {
+            budget_category_id,
+            percent,
+        } => translation_provider
+            .alert_nearly_spent(category_name(budget_category_id), percent.to_string()),
+        Alert::OverBudget {
+            budget_category_id,
+            spent,
+            budget,
+        } => translation_provider.alert_over_budget(
+            category_name(budget_category_id),
+            format_amount(*spent, translation_provider),
+            format_amount(*budget, translation_provider),
+        ),
+        Alert::NegativeTotal { total } => {
+            translation_provider.alert_negative_total(format_amount(*total, translation_provider))
+        }
+        Alert::LargeTransaction { name, date, amount } => translation_provider
+            .alert_large_spending(
+                name.clone(),
+                format_amount(*amount, translation_provider),
+                date.format(DATE_FORMAT).to_string(),
+            ),
+    }
+    .expect("Could not format the alert!")
+}
+
+pub enum AlertsListStoreIds {
+    Message = 0,
+    BackgroundColor = 1,
+}
+
+impl Into<i32> for AlertsListStoreIds {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+impl Into<u32> for AlertsListStoreIds {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+pub fn get_alerts_model(
+    alerts: &[Alert],
+    budget_categories: &BudgetCategories,
+    translation_provider: &TranslationProvider,
+) -> gtk::ListStore {
+    use AlertsListStoreIds::*;
+    let list = gtk::ListStore::new(&[
+        // message
+        String::static_type(),
+        // background color
+        String::static_type(),
+    ]);
+    for alert in alerts {
+        let background_color = match alert {
+            Alert::OverBudget { .. } | Alert::NegativeTotal { .. } => {
+                BACKGROUND_COLOR_WRONG_BUDGET_CATEGORY
+            }
+            Alert::NearlySpent { .. } | Alert::LargeTransaction { .. } => BACKGROUND_COLOR_NORMAL,
+        };
+        list.insert_with_values(
+            None,
+            &[Message.into(), BackgroundColor.into()],
+            &[
+                &alert_message(alert, budget_categories, translation_provider),
+                &background_color,
+            ],
+        );
+    }
+    list
+}
+
+pub fn add_default_spending(model: &gtk::TreeStore, date: chrono::NaiveDate) {
+    use SpendingsGtkModelIds::*;
+    model.insert_with_values(
+        None,
+        None,
+        &[
             Name.into(),
             CategoryName.into(),
             Amount.into(),
-            Day.into(),
+            Date.into(),
             NameBackgroundColor.into(),
             CategoryNameBackgroundColor.into(),
             AmountBackgroundColor.into(),
-            DayBackgroundColor.into(),
+            DateBackgroundColor.into(),
+            Tags.into(),
+            Note.into(),
+            SpendingCellsVisible.into(),
+            Editable.into(),
+            PaidBy.into(),
+            Share.into(),
+            Reimbursement.into(),
+            AmountCents.into(),
+            IsDefault.into(),
         ],
         &[
             &"New spending",
@@ -319,18 +2155,109 @@ pub fn add_default_spending(model: &gtk::ListStore, today: Day) {
             // note: using an option would be better...
             &"",
             &"",
-            &today.0,
+            &date.format(DATE_FORMAT).to_string(),
             &BACKGROUND_COLOR_IS_DEFAULT,
             &BACKGROUND_COLOR_IS_DEFAULT,
             &BACKGROUND_COLOR_IS_DEFAULT,
             &BACKGROUND_COLOR_IS_DEFAULT,
+            &"",
+            &"",
+            &true,
+            &true,
+            &"",
+            &"",
+            &"",
+            &0,
+            &true,
         ],
     );
 }
 
-pub fn add_default_budget_category(model: &gtk::ListStore) {
-    use BudgetCategoriesListStoreIds::*;
+// the view's model: the store filtered by the quick-filter text (on the spendings' name,
+// category, tags and note), then sorted by date unless a column header is clicked
+pub fn get_sorted_spendings_model(
+    spendings_store: &gtk::TreeStore,
+    filter_text: &str,
+) -> gtk::TreeModelSort {
+    use SpendingsGtkModelIds::*;
+    let filter = gtk::TreeModelFilter::new(spendings_store, None);
+    let filter_text = filter_text.trim().to_lowercase();
+    filter.set_visible_func(move |model, iter| {
+        // the split lines go with their spending
+        if filter_text.is_empty()
+            || model.iter_parent(iter).is_some()
+            || model.get_value(iter, IsDefault.into()).get::<bool>() == Some(true)
+        {
+            return true;
+        }
+        [Name as i32, CategoryName as i32, Tags as i32, Note as i32]
+            .iter()
+            .any(|column| {
+                model
+                    .get_value(iter, *column)
+                    .get::<String>()
+                    .map_or(false, |text| text.to_lowercase().contains(&filter_text))
+            })
+    });
+
+    let sort = gtk::TreeModelSort::new(&filter);
+    for column in &[Name as i32, CategoryName as i32, Amount as i32, Date as i32] {
+        let column = *column;
+        // gtk hands the child model (i.e. the filter) to the sort function, the order can only be
+        // read from the sort model itself
+        let weak_sort = sort.downgrade();
+        sort.set_sort_func(gtk::SortColumn::Index(column as u32), move |model, a, b| {
+            let descending = weak_sort
+                .upgrade()
+                .and_then(|sort| sort.get_sort_column_id())
+                .map_or(false, |(_, order)| order == gtk::SortType::Descending);
+            compare_spendings_rows(model.upcast_ref(), a, b, column, descending)
+        });
+    }
+    sort.set_sort_column_id(
+        gtk::SortColumn::Index(Date.into()),
+        gtk::SortType::Ascending,
+    );
+    sort
+}
+
+fn compare_spendings_rows(
+    model: &gtk::TreeModel,
+    a: &gtk::TreeIter,
+    b: &gtk::TreeIter,
+    column: i32,
+    descending: bool,
+) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    use SpendingsGtkModelIds::*;
+    let is_default = |iter| model.get_value(iter, IsDefault.into()).get::<bool>() == Some(true);
+    // gtk reverses the result for the descending order, the default row has to stay last anyway
+    match (is_default(a), is_default(b)) {
+        (true, false) if descending => return Ordering::Less,
+        (true, false) => return Ordering::Greater,
+        (false, true) if descending => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        _ => (),
+    }
+
+    if column == Amount as i32 {
+        let amount = |iter| model.get_value(iter, AmountCents.into()).get::<i32>();
+        amount(a).cmp(&amount(b))
+    } else {
+        let text = |iter| {
+            model
+                .get_value(iter, column)
+                .get::<String>()
+                .map(|text| text.to_lowercase())
+        };
+        text(a).cmp(&text(b))
+    }
+}
+
+pub fn add_default_budget_category(model: &gtk::TreeStore) {
+    use BudgetCategoriesTreeStoreIds::*;
     model.insert_with_values(
+        None,
         None,
         &[
             Name.into(),
@@ -339,6 +2266,9 @@ pub fn add_default_budget_category(model: &gtk::ListStore) {
             NameBackgroundColor.into(),
             AmountBackgroundColor.into(),
             BalanceBackgroundColor.into(),
+            ParentName.into(),
+            ParentNameBackgroundColor.into(),
+            Id.into(),
         ],
         &[
             &"New category",
@@ -347,30 +2277,13 @@ pub fn add_default_budget_category(model: &gtk::ListStore) {
             &BACKGROUND_COLOR_IS_DEFAULT,
             &BACKGROUND_COLOR_IS_DEFAULT,
             &BACKGROUND_COLOR_IS_DEFAULT,
+            &"",
+            &BACKGROUND_COLOR_IS_DEFAULT,
+            &NO_BUDGET_CATEGORY_ID,
         ],
     );
 }
 
-pub fn order_spendings_by_day(model: &gtk::ListStore) {
-    let mut sorting_by_day_vec = Vec::new();
-    let mut i = 0;
-    model.foreach(|m, _, iter| {
-        let day = m
-            .get_value(iter, SpendingsGtkModelIds::Day.into())
-            .get::<i32>()
-            .unwrap();
-        sorting_by_day_vec.push((i, day));
-        i += 1;
-        false
-    });
-    sorting_by_day_vec.sort_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap());
-    let ordering_array = sorting_by_day_vec
-        .iter()
-        .map(|(i, _)| *i as u32)
-        .collect::<Vec<_>>();
-    model.reorder(&ordering_array);
-}
-
 pub fn amount_to_color(amount: i32) -> &'static str {
     match amount.cmp(&0) {
         std::cmp::Ordering::Greater => BACKGROUND_COLOR_CREDIT,
@@ -378,3 +2291,14 @@ pub fn amount_to_color(amount: i32) -> &'static str {
         std::cmp::Ordering::Less => BACKGROUND_COLOR_DEBIT,
     }
 }
+
+pub fn format_amount(amount: i32, translation_provider: &TranslationProvider) -> String {
+    let amount = MoneyAmount::from_i32(amount);
+    translation_provider
+        .format_money(
+            amount.sign(),
+            amount.whole_with_separator(&translation_provider.thousands_separator()),
+            amount.cents_padded(),
+        )
+        .expect("Could not format the input in the format_amount fn!")
+}
@@ -0,0 +1,117 @@
+use crate::data::{Month, MonthlyBudget, Spending, SpendingId, Year};
+
+// how far apart (in days) two spendings can be and still be considered duplicates
+pub const DUPLICATE_DAY_TOLERANCE: i64 = 3;
+
+// the spending a duplicate was found for, in the same month or an adjacent one
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DuplicateOf {
+    SameMonth(SpendingId),
+    OtherMonth(SpendingId),
+}
+
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + if a_char == b_char { 0 } else { 1 };
+            row[j + 1] = substitution.min(previous_row[j + 1] + 1).min(row[j] + 1);
+        }
+        previous_row = row;
+    }
+    previous_row[b.len()]
+}
+
+// "AMAZON MKTP" and "Amazon" are similar, so are "Starbucks" and "Starbuck"
+pub fn are_names_similar(a: &str, b: &str) -> bool {
+    let a = normalize_name(a);
+    let b = normalize_name(b);
+    if a.is_empty() || b.is_empty() {
+        return a == b;
+    }
+    if a.contains(&b) || b.contains(&a) {
+        return true;
+    }
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    // allow roughly one typo every four characters
+    edit_distance(&a, &b) <= a.len().max(b.len()) / 4
+}
+
+// the user told us these two are not duplicates, even though they look like it
+fn is_dismissed(a: &Spending, b: &Spending) -> bool {
+    a.dismissed_duplicates.contains(&b.id) || b.dismissed_duplicates.contains(&a.id)
+}
+
+fn are_duplicates(a: &Spending, b: &Spending) -> bool {
+    !is_dismissed(a, b)
+        && a.amount.to_i32() == b.amount.to_i32()
+        && (a.date - b.date).num_days().abs() <= DUPLICATE_DAY_TOLERANCE
+        && are_names_similar(&a.name, &b.name)
+}
+
+// one entry per spending of the month, the other months should be the adjacent ones
+pub fn find_duplicates(
+    monthly_budget: &MonthlyBudget,
+    other_monthly_budgets: &[(Month, Year, MonthlyBudget)],
+) -> Vec<Option<DuplicateOf>> {
+    let spendings = &monthly_budget.spendings.0;
+    spendings
+        .iter()
+        .enumerate()
+        .map(|(idx, spending)| {
+            let same_month = spendings
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, _)| *other_idx != idx)
+                .find(|(_, other)| are_duplicates(spending, other))
+                .map(|(_, other)| DuplicateOf::SameMonth(other.id));
+            if same_month.is_some() {
+                return same_month;
+            }
+
+            other_monthly_budgets
+                .iter()
+                .filter_map(|(_, _, other_monthly_budget)| {
+                    other_monthly_budget
+                        .spendings
+                        .0
+                        .iter()
+                        .find(|other| are_duplicates(spending, other))
+                        .map(|other| DuplicateOf::OtherMonth(other.id))
+                })
+                .next()
+        })
+        .collect()
+}
+
+// the kept spending gets the tags and note of the removed one
+pub fn merge_into(kept: &mut Spending, removed: Spending) {
+    for tag in removed.tags {
+        if !kept.has_tag(&tag) {
+            kept.tags.push(tag);
+        }
+    }
+    if !removed.note.is_empty() && removed.note != kept.note {
+        if !kept.note.is_empty() {
+            kept.note.push_str("; ");
+        }
+        kept.note.push_str(&removed.note);
+    }
+    if kept.splits.is_empty() {
+        kept.splits = removed.splits;
+    }
+    for dismissed_id in removed.dismissed_duplicates {
+        if dismissed_id != kept.id && !kept.dismissed_duplicates.contains(&dismissed_id) {
+            kept.dismissed_duplicates.push(dismissed_id);
+        }
+    }
+}
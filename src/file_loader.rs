@@ -1,13 +1,30 @@
+use crate::bills::Bills;
 use crate::config::Config;
-use crate::data::{BudgetCategories, Month, MonthlyBudget, Year};
+use crate::data::{
+    date_in_month, month_year_of, BudgetCategories, BudgetCategoryParents, Members, Month,
+    MonthlyBudget, Payees, PeriodBudget, SpendingId, Year,
+};
+use crate::goals::Goals;
+use crate::loans::Loans;
+use crate::net_worth::Valuations;
+use crate::periods::BudgetPeriod;
+use crate::rules::Rules;
 use serde_json;
 use std::error::Error;
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 
+const BILLS_FILE: &str = "bills.json";
 const BUDGET_CATEGORIES_FILE: &str = "budget_categories.json";
+const BUDGET_CATEGORY_PARENTS_FILE: &str = "budget_category_parents.json";
 const CONFIG_FILE: &str = "config.json";
+const GOALS_FILE: &str = "goals.json";
+const LOANS_FILE: &str = "loans.json";
+const MEMBERS_FILE: &str = "members.json";
+const PAYEES_FILE: &str = "payees.json";
+const RULES_FILE: &str = "rules.json";
+const VALUATIONS_FILE: &str = "valuations.json";
 
 pub struct FileLoader {
     base_dir: PathBuf,
@@ -34,15 +51,81 @@ impl FileLoader {
         FileLoader::load_or_default(config_path)
     }
 
+    pub fn load_bills(&self) -> Result<Bills, Box<dyn Error>> {
+        let mut bills_path = self.base_dir.clone();
+        bills_path.push(BILLS_FILE);
+        FileLoader::load_or_default(bills_path)
+    }
+
     pub fn load_budget_categories(&self) -> Result<BudgetCategories, Box<dyn Error>> {
         FileLoader::load_or_default(&self.budget_categories_path)
     }
 
+    pub fn load_budget_category_parents(&self) -> Result<BudgetCategoryParents, Box<dyn Error>> {
+        let mut budget_category_parents_path = self.base_dir.clone();
+        budget_category_parents_path.push(BUDGET_CATEGORY_PARENTS_FILE);
+        FileLoader::load_or_default(budget_category_parents_path)
+    }
+
+    pub fn load_payees(&self) -> Result<Payees, Box<dyn Error>> {
+        let mut payees_path = self.base_dir.clone();
+        payees_path.push(PAYEES_FILE);
+        FileLoader::load_or_default(payees_path)
+    }
+
+    pub fn load_rules(&self) -> Result<Rules, Box<dyn Error>> {
+        let mut rules_path = self.base_dir.clone();
+        rules_path.push(RULES_FILE);
+        FileLoader::load_or_default(rules_path)
+    }
+
+    pub fn load_goals(&self) -> Result<Goals, Box<dyn Error>> {
+        let mut goals_path = self.base_dir.clone();
+        goals_path.push(GOALS_FILE);
+        FileLoader::load_or_default(goals_path)
+    }
+
+    pub fn load_valuations(&self) -> Result<Valuations, Box<dyn Error>> {
+        let mut valuations_path = self.base_dir.clone();
+        valuations_path.push(VALUATIONS_FILE);
+        FileLoader::load_or_default(valuations_path)
+    }
+
+    pub fn load_loans(&self) -> Result<Loans, Box<dyn Error>> {
+        let mut loans_path = self.base_dir.clone();
+        loans_path.push(LOANS_FILE);
+        FileLoader::load_or_default(loans_path)
+    }
+
+    pub fn load_members(&self) -> Result<Members, Box<dyn Error>> {
+        let mut members_path = self.base_dir.clone();
+        members_path.push(MEMBERS_FILE);
+        FileLoader::load_or_default(members_path)
+    }
+
     pub fn load_monthly_budget(&self, m: Month, y: Year) -> Result<MonthlyBudget, Box<dyn Error>> {
         let mut monthly_budget_path = self.base_dir.clone();
         monthly_budget_path.push(FileLoader::month_year_to_filename(m, y));
 
-        FileLoader::load_or_default(monthly_budget_path)
+        let mut monthly_budget: MonthlyBudget =
+            FileLoader::load_or_default(monthly_budget_path.clone())?;
+        // older files only stored the day, the rest of the date is given by the file
+        let mut ids_assigned = false;
+        for spending in &mut monthly_budget.spendings.0 {
+            if let Some(day) = spending.legacy_day.take() {
+                spending.date = date_in_month(m, y, day.0 as u32);
+            }
+            if !spending.id.is_assigned() {
+                spending.id = SpendingId::new();
+                ids_assigned = true;
+            }
+        }
+        // unlike the dates, the new ids are written back right away so that every load of the
+        // file gives the same ones
+        if ids_assigned {
+            FileLoader::save(monthly_budget_path, &monthly_budget)?;
+        }
+        Ok(monthly_budget)
     }
 
     fn load_or_default<T: serde::de::DeserializeOwned + Default, P: Into<PathBuf>>(
@@ -64,6 +147,12 @@ impl FileLoader {
         FileLoader::save(&config_path, config)
     }
 
+    pub fn save_bills(&self, bills: &Bills) -> Result<(), Box<dyn Error>> {
+        let mut bills_path = self.base_dir.clone();
+        bills_path.push(BILLS_FILE);
+        FileLoader::save(bills_path, bills)
+    }
+
     pub fn save_budget_categories(
         &self,
         budget_categories: &BudgetCategories,
@@ -71,6 +160,51 @@ impl FileLoader {
         FileLoader::save(&self.budget_categories_path, budget_categories)
     }
 
+    pub fn save_budget_category_parents(
+        &self,
+        budget_category_parents: &BudgetCategoryParents,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut budget_category_parents_path = self.base_dir.clone();
+        budget_category_parents_path.push(BUDGET_CATEGORY_PARENTS_FILE);
+        FileLoader::save(budget_category_parents_path, budget_category_parents)
+    }
+
+    pub fn save_payees(&self, payees: &Payees) -> Result<(), Box<dyn Error>> {
+        let mut payees_path = self.base_dir.clone();
+        payees_path.push(PAYEES_FILE);
+        FileLoader::save(payees_path, payees)
+    }
+
+    pub fn save_rules(&self, rules: &Rules) -> Result<(), Box<dyn Error>> {
+        let mut rules_path = self.base_dir.clone();
+        rules_path.push(RULES_FILE);
+        FileLoader::save(rules_path, rules)
+    }
+
+    pub fn save_goals(&self, goals: &Goals) -> Result<(), Box<dyn Error>> {
+        let mut goals_path = self.base_dir.clone();
+        goals_path.push(GOALS_FILE);
+        FileLoader::save(goals_path, goals)
+    }
+
+    pub fn save_valuations(&self, valuations: &Valuations) -> Result<(), Box<dyn Error>> {
+        let mut valuations_path = self.base_dir.clone();
+        valuations_path.push(VALUATIONS_FILE);
+        FileLoader::save(valuations_path, valuations)
+    }
+
+    pub fn save_loans(&self, loans: &Loans) -> Result<(), Box<dyn Error>> {
+        let mut loans_path = self.base_dir.clone();
+        loans_path.push(LOANS_FILE);
+        FileLoader::save(loans_path, loans)
+    }
+
+    pub fn save_members(&self, members: &Members) -> Result<(), Box<dyn Error>> {
+        let mut members_path = self.base_dir.clone();
+        members_path.push(MEMBERS_FILE);
+        FileLoader::save(members_path, members)
+    }
+
     pub fn save_monthly_budget(
         &self,
         m: Month,
@@ -83,13 +217,121 @@ impl FileLoader {
         FileLoader::save(monthly_budget_path, monthly_budget)
     }
 
+    // a period which isn't a calendar month gathers its spendings from the month files it
+    // overlaps
+    pub fn load_period_budget(
+        &self,
+        period: &BudgetPeriod,
+    ) -> Result<MonthlyBudget, Box<dyn Error>> {
+        if let Some((m, y)) = period.as_calendar_month() {
+            return self.load_monthly_budget(m, y);
+        }
+
+        let mut period_budget = MonthlyBudget::default();
+        for (m, y) in period.months() {
+            let mut monthly_budget = self.load_monthly_budget(m, y)?;
+            if let Some(budgets) = monthly_budget.period_budgets.remove(&period.start) {
+                period_budget.budgets = budgets.budgets;
+                period_budget.envelope_transfers = budgets.envelope_transfers;
+            }
+            period_budget.spendings.0.extend(
+                monthly_budget
+                    .spendings
+                    .0
+                    .into_iter()
+                    .filter(|spending| period.contains(spending.date)),
+            );
+        }
+        Ok(period_budget)
+    }
+
+    pub fn save_period_budget(
+        &self,
+        period: &BudgetPeriod,
+        period_budget: &MonthlyBudget,
+    ) -> Result<(), Box<dyn Error>> {
+        if let Some((m, y)) = period.as_calendar_month() {
+            return self.save_monthly_budget(m, y, period_budget);
+        }
+
+        let (start_m, start_y) = month_year_of(period.start);
+        for (m, y) in period.months() {
+            let mut monthly_budget = self.load_monthly_budget(m, y)?;
+            // the spendings of the period are replaced, the others are left untouched
+            monthly_budget
+                .spendings
+                .0
+                .retain(|spending| !period.contains(spending.date));
+            monthly_budget.spendings.0.extend(
+                period_budget
+                    .spendings
+                    .0
+                    .iter()
+                    .filter(|spending| month_year_of(spending.date) == (m, y))
+                    .cloned(),
+            );
+            if m == start_m && y == start_y {
+                monthly_budget.period_budgets.insert(
+                    period.start,
+                    PeriodBudget {
+                        budgets: period_budget.budgets.clone(),
+                        envelope_transfers: period_budget.envelope_transfers.clone(),
+                    },
+                );
+            }
+            self.save_monthly_budget(m, y, &monthly_budget)?;
+        }
+        Ok(())
+    }
+
     fn save<T: serde::Serialize, P: AsRef<Path>>(path: P, t: T) -> Result<(), Box<dyn Error>> {
         let file = File::create(path)?;
         let writer = BufWriter::new(file);
         Ok(serde_json::to_writer(writer, &t)?)
     }
 
+    // every month for which a file exists, in chronological order
+    pub fn list_monthly_budgets(&self) -> Result<Vec<(Month, Year)>, Box<dyn Error>> {
+        let mut months = Vec::new();
+        for entry in std::fs::read_dir(&self.base_dir)? {
+            let file_name = entry?.file_name();
+            if let Some((m, y)) = file_name
+                .to_str()
+                .and_then(FileLoader::filename_to_month_year)
+            {
+                months.push((m, y));
+            }
+        }
+        months.sort_by_key(|(m, y)| (y.0, m.id()));
+        Ok(months)
+    }
+
+    pub fn load_all_monthly_budgets(
+        &self,
+    ) -> Result<Vec<(Month, Year, MonthlyBudget)>, Box<dyn Error>> {
+        let mut monthly_budgets = Vec::new();
+        for (m, y) in self.list_monthly_budgets()? {
+            monthly_budgets.push((m, y, self.load_monthly_budget(m, y)?));
+        }
+        Ok(monthly_budgets)
+    }
+
     fn month_year_to_filename(m: Month, y: Year) -> String {
         y.to_string() + "_" + &format!("{:02}", m.id()) + ".json"
     }
+
+    fn filename_to_month_year(filename: &str) -> Option<(Month, Year)> {
+        let stem = filename.trim_end_matches(".json");
+        if stem.len() == filename.len() {
+            return None;
+        }
+        let mut split = stem.split('_');
+        let y = split.next()?.parse::<u32>().ok()?;
+        let m = split.next()?.parse::<u32>().ok()?;
+        if split.next().is_some() || m == 0 {
+            return None;
+        }
+        let m = num_traits::FromPrimitive::from_u32(m - 1)?;
+        Some((m, Year(y)))
+    }
 }
@@ -0,0 +1,273 @@
+use crate::data::{
+    date_in_month, month_year_of, next_month, previous_month, BudgetCategoryId, Month,
+    MonthlyBudget, Spending, Year,
+};
+use crate::duplicates::are_names_similar;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::{BTreeSet, HashMap};
+
+// how many complete months before the current one are used for the averages
+pub const HISTORY_MONTHS: usize = 6;
+// a spending seen in this many of the history months is expected to come back every month
+pub const RECURRING_MIN_MONTHS: usize = 3;
+
+// e.g. the rent or the salary
+#[derive(Debug, Clone)]
+pub struct RecurringItem {
+    pub name: String,
+    pub budget_category_id: BudgetCategoryId,
+    pub day: u32,
+    pub amount: i32,
+}
+
+impl RecurringItem {
+    fn matches(&self, spending: &Spending) -> bool {
+        spending.amount.to_i32() == self.amount && are_names_similar(&spending.name, &self.name)
+    }
+}
+
+pub struct ForecastMonth {
+    pub month: Month,
+    pub year: Year,
+    pub income: i32,
+    pub expenses: i32,
+    // at the end of the month
+    pub balance: i32,
+}
+
+pub struct Forecast {
+    pub months: Vec<ForecastMonth>,
+    pub lowest_balance: i32,
+    pub lowest_date: NaiveDate,
+}
+
+fn find_monthly_budget(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    m: Month,
+    y: Year,
+) -> Option<&MonthlyBudget> {
+    monthly_budgets
+        .iter()
+        .find(|(other_m, other_y, _)| *other_m == m && *other_y == y)
+        .map(|(_, _, monthly_budget)| monthly_budget)
+}
+
+// the months before the one of today, the most recent first
+fn history_months(today: NaiveDate) -> Vec<(Month, Year)> {
+    let (mut m, mut y) = month_year_of(today);
+    let mut months = Vec::new();
+    for _ in 0..HISTORY_MONTHS {
+        let previous = previous_month(m, y);
+        m = previous.0;
+        y = previous.1;
+        months.push((m, y));
+    }
+    months
+}
+
+// spendings with the same amount and a similar name, found in enough distinct months
+pub fn find_recurring_items(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    today: NaiveDate,
+) -> Vec<RecurringItem> {
+    // each candidate keeps the months it was seen in
+    let mut candidates: Vec<(RecurringItem, BTreeSet<usize>)> = Vec::new();
+    for (month_idx, (m, y)) in history_months(today).into_iter().enumerate() {
+        let monthly_budget = match find_monthly_budget(monthly_budgets, m, y) {
+            Some(monthly_budget) => monthly_budget,
+            None => continue,
+        };
+        for spending in &monthly_budget.spendings.0 {
+            // the split spendings are too irregular to be predicted
+            if !spending.splits.is_empty() {
+                continue;
+            }
+            match candidates
+                .iter_mut()
+                .find(|(item, _)| item.matches(spending))
+            {
+                Some((_, months)) => {
+                    months.insert(month_idx);
+                }
+                None => {
+                    let mut months = BTreeSet::new();
+                    months.insert(month_idx);
+                    candidates.push((
+                        RecurringItem {
+                            name: spending.name.clone(),
+                            budget_category_id: spending.budget_category_id,
+                            day: spending.date.day(),
+                            amount: spending.amount.to_i32(),
+                        },
+                        months,
+                    ));
+                }
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|(_, months)| months.len() >= RECURRING_MIN_MONTHS)
+        .map(|(item, _)| item)
+        .collect()
+}
+
+// the average net amount per month of each category, without the recurring items
+fn category_averages(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    recurring_items: &[RecurringItem],
+    today: NaiveDate,
+) -> HashMap<BudgetCategoryId, i32> {
+    let mut totals = HashMap::new();
+    let mut month_count = 0;
+    for (m, y) in history_months(today) {
+        let monthly_budget = match find_monthly_budget(monthly_budgets, m, y) {
+            Some(monthly_budget) => monthly_budget,
+            None => continue,
+        };
+        month_count += 1;
+        for spending in &monthly_budget.spendings.0 {
+            if recurring_items.iter().any(|item| item.matches(spending)) {
+                continue;
+            }
+            for (budget_category_id, amount) in spending.amounts_per_category() {
+                *totals.entry(budget_category_id).or_insert(0) += amount;
+            }
+        }
+    }
+    if month_count > 0 {
+        for total in totals.values_mut() {
+            *total /= month_count;
+        }
+    }
+    totals
+}
+
+// every spending recorded up to today
+pub fn current_balance(monthly_budgets: &[(Month, Year, MonthlyBudget)], today: NaiveDate) -> i32 {
+    monthly_budgets
+        .iter()
+        .flat_map(|(_, _, monthly_budget)| &monthly_budget.spendings.0)
+        .filter(|spending| spending.date <= today)
+        .map(|spending| spending.amount.to_i32())
+        .sum()
+}
+
+// the balance at the end of each of the next months, the current one included
+// recurring items are expected on their day; the rest of the expenses is expected as soon as
+// possible and the rest of the income as late as possible, so that the lowest point is not
+// underestimated
+pub fn forecast(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    today: NaiveDate,
+    month_count: usize,
+) -> Forecast {
+    let recurring_items = find_recurring_items(monthly_budgets, today);
+    let averages = category_averages(monthly_budgets, &recurring_items, today);
+    let is_recurring_category = |budget_category_id: &BudgetCategoryId| {
+        recurring_items
+            .iter()
+            .any(|item| item.budget_category_id == *budget_category_id)
+    };
+
+    let mut balance = current_balance(monthly_budgets, today);
+    let mut lowest_balance = balance;
+    let mut lowest_date = today;
+    let mut months = Vec::new();
+    let (mut m, mut y) = month_year_of(today);
+    for month_idx in 0..month_count {
+        let first_day = if month_idx == 0 {
+            today + Duration::days(1)
+        } else {
+            date_in_month(m, y, 1)
+        };
+        let last_day = date_in_month(m, y, 31);
+        let monthly_budget = find_monthly_budget(monthly_budgets, m, y);
+
+        // (date, amount)
+        let mut flows = Vec::new();
+        for item in &recurring_items {
+            let date = date_in_month(m, y, item.day);
+            let already_recorded = monthly_budget.map_or(false, |monthly_budget| {
+                monthly_budget
+                    .spendings
+                    .0
+                    .iter()
+                    .any(|spending| item.matches(spending))
+            });
+            if date >= first_day && !already_recorded {
+                flows.push((date, item.amount));
+            }
+        }
+
+        // what was already recorded for the month, per category
+        let mut recorded = HashMap::new();
+        if let Some(monthly_budget) = monthly_budget {
+            for spending in &monthly_budget.spendings.0 {
+                if recurring_items.iter().any(|item| item.matches(spending)) {
+                    continue;
+                }
+                for (budget_category_id, amount) in spending.amounts_per_category() {
+                    *recorded.entry(budget_category_id).or_insert(0) += amount;
+                }
+            }
+        }
+        let mut expected = averages.clone();
+        // this month's budgets are a better guess than the history
+        if month_idx == 0 {
+            if let Some(monthly_budget) = monthly_budget {
+                for (budget_category_id, budget_amount) in &monthly_budget.budgets {
+                    expected.insert(
+                        *budget_category_id,
+                        -(budget_amount.0 + monthly_budget.net_transfers(*budget_category_id)),
+                    );
+                }
+            }
+        }
+        for (budget_category_id, expected_amount) in expected {
+            if is_recurring_category(&budget_category_id) {
+                continue;
+            }
+            let rest = expected_amount - recorded.get(&budget_category_id).cloned().unwrap_or(0);
+            // more of the same is expected, never a refund
+            if rest == 0 || rest.signum() != expected_amount.signum() {
+                continue;
+            }
+            flows.push((if rest < 0 { first_day } else { last_day }, rest));
+        }
+        // on the same day, the expenses go first
+        flows.sort_by_key(|(date, amount)| (*date, *amount));
+
+        let mut income = 0;
+        let mut expenses = 0;
+        for (date, amount) in flows {
+            if amount > 0 {
+                income += amount;
+            } else {
+                expenses += amount;
+            }
+            balance += amount;
+            if balance < lowest_balance {
+                lowest_balance = balance;
+                lowest_date = date;
+            }
+        }
+        months.push(ForecastMonth {
+            month: m,
+            year: y,
+            income,
+            expenses,
+            balance,
+        });
+
+        let next = next_month(m, y);
+        m = next.0;
+        y = next.1;
+    }
+
+    Forecast {
+        months,
+        lowest_balance,
+        lowest_date,
+    }
+}
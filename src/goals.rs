@@ -0,0 +1,135 @@
+use crate::data::{
+    date_in_month, month_year_of, BudgetCategoryId, Month, MonthlyBudget, Year, DATE_FORMAT,
+};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// e.g. "Holiday: 2000.00 by 2027-06", saved in the envelope of a category
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SavingsGoal {
+    pub name: String,
+    // in cents
+    pub target: i32,
+    pub deadline: NaiveDate,
+    #[serde(default)]
+    pub budget_category_id: Option<BudgetCategoryId>,
+    // when the goal was created, the schedule starts from there
+    pub start: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Goals(pub Vec<SavingsGoal>);
+
+// the number of month changes between both dates, the days are ignored
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32
+}
+
+// accepts a full date, or only a month ("2027-06") which means its last day
+pub fn parse_deadline(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    NaiveDate::parse_from_str(text, DATE_FORMAT)
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{}-01", text), DATE_FORMAT))
+        .ok()
+        .map(|date| {
+            if text.len() < 8 {
+                let (m, y) = month_year_of(date);
+                date_in_month(m, y, 31)
+            } else {
+                date
+            }
+        })
+}
+
+impl SavingsGoal {
+    pub fn new(name: String, start: NaiveDate) -> Self {
+        SavingsGoal {
+            name,
+            target: 0,
+            deadline: start,
+            budget_category_id: None,
+            start,
+        }
+    }
+
+    // what the category's envelope holds: the budgets, the transfers and the spendings (deposits
+    // are credits) of every month up to today's
+    pub fn saved(&self, monthly_budgets: &[(Month, Year, MonthlyBudget)], today: NaiveDate) -> i32 {
+        let budget_category_id = match self.budget_category_id {
+            Some(budget_category_id) => budget_category_id,
+            None => return 0,
+        };
+        monthly_budgets
+            .iter()
+            .filter(|(m, y, _)| date_in_month(*m, *y, 1) <= today)
+            .map(|(_, _, monthly_budget)| {
+                let budgeted = monthly_budget
+                    .budgets
+                    .get(&budget_category_id)
+                    .map_or(0, |amount| amount.0)
+                    + monthly_budget
+                        .period_budgets
+                        .values()
+                        .filter_map(|period_budget| period_budget.budgets.get(&budget_category_id))
+                        .map(|amount| amount.0)
+                        .sum::<i32>();
+                let period_transfers = monthly_budget
+                    .period_budgets
+                    .values()
+                    .flat_map(|period_budget| &period_budget.envelope_transfers)
+                    .map(|transfer| {
+                        if transfer.to == budget_category_id {
+                            transfer.amount.0
+                        } else if transfer.from == budget_category_id {
+                            -transfer.amount.0
+                        } else {
+                            0
+                        }
+                    })
+                    .sum::<i32>();
+                let spent = monthly_budget
+                    .spendings
+                    .0
+                    .iter()
+                    .filter(|spending| spending.date <= today)
+                    .flat_map(|spending| spending.amounts_per_category())
+                    .filter(|(id, _)| *id == budget_category_id)
+                    .map(|(_, amount)| amount)
+                    .sum::<i32>();
+                budgeted
+                    + monthly_budget.net_transfers(budget_category_id)
+                    + period_transfers
+                    + spent
+            })
+            .sum()
+    }
+
+    // between 0 and 1
+    pub fn progress(&self, saved: i32) -> f64 {
+        if self.target <= 0 {
+            return 1.0;
+        }
+        (f64::from(saved) / f64::from(self.target))
+            .max(0.0)
+            .min(1.0)
+    }
+
+    // what is left to save, spread over the months until the deadline (this one included)
+    pub fn required_monthly_contribution(&self, saved: i32, today: NaiveDate) -> i32 {
+        let remaining = (self.target - saved).max(0);
+        let months_left = (months_between(today, self.deadline) + 1).max(1);
+        // rounded up, so that the target is reached in time
+        (remaining + months_left - 1) / months_left
+    }
+
+    // what should have been saved by now, with the same contribution every month
+    pub fn expected_saved(&self, today: NaiveDate) -> i32 {
+        let total_months = i64::from((months_between(self.start, self.deadline) + 1).max(1));
+        let elapsed_months = i64::from(months_between(self.start, today).max(0)).min(total_months);
+        (i64::from(self.target) * elapsed_months / total_months) as i32
+    }
+
+    pub fn is_behind_schedule(&self, saved: i32, today: NaiveDate) -> bool {
+        saved < self.expected_saved(today)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::data::{date_in_month, month_year_of, next_month, BudgetCategoryId};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// a payment on top of the scheduled ones, which shortens the rest of the schedule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExtraPayment {
+    pub date: NaiveDate,
+    // in cents
+    pub amount: i32,
+}
+
+// a loan or a mortgage, repaid with a fixed payment every month
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Loan {
+    pub name: String,
+    // in cents
+    pub principal: i32,
+    // yearly, in percent
+    pub annual_rate: f64,
+    pub term_months: u32,
+    // when the money was borrowed, the first payment is the month after
+    pub start: NaiveDate,
+    pub payment_day: u32,
+    #[serde(default)]
+    pub budget_category_id: Option<BudgetCategoryId>,
+    #[serde(default)]
+    pub extra_payments: Vec<ExtraPayment>,
+    // the payments up to this date already have their spending
+    #[serde(default)]
+    pub paid_through: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Loans(pub Vec<Loan>);
+
+pub struct ScheduledPayment {
+    // starts at 1
+    pub number: u32,
+    pub date: NaiveDate,
+    // what is paid on the date, interest included
+    pub payment: i32,
+    pub interest: i32,
+    pub principal: i32,
+    // the extra payments made since the previous payment
+    pub extra: i32,
+    // what is left to repay after this payment
+    pub remaining: i32,
+}
+
+impl Loan {
+    pub fn new(name: String, start: NaiveDate) -> Self {
+        Loan {
+            name,
+            principal: 0,
+            annual_rate: 0.0,
+            term_months: 12,
+            start,
+            payment_day: 1,
+            budget_category_id: None,
+            extra_payments: Vec::new(),
+            // the payments made before the loan was entered are already in the month files
+            paid_through: Some(start),
+        }
+    }
+
+    fn monthly_rate(&self) -> f64 {
+        self.annual_rate / 100.0 / 12.0
+    }
+
+    // the usual annuity formula, rounded up to the cent so that the term is never exceeded
+    pub fn monthly_payment(&self) -> i32 {
+        let term_months = f64::from(self.term_months.max(1));
+        let principal = f64::from(self.principal);
+        let rate = self.monthly_rate();
+        let payment = if rate == 0.0 {
+            principal / term_months
+        } else {
+            principal * rate / (1.0 - (1.0 + rate).powf(-term_months))
+        };
+        payment.ceil() as i32
+    }
+
+    // the monthly payment stays the same, the extra payments make the loan end earlier
+    pub fn schedule(&self) -> Vec<ScheduledPayment> {
+        let monthly_payment = self.monthly_payment();
+        let rate = self.monthly_rate();
+        let mut schedule = Vec::new();
+        let mut remaining = self.principal;
+        let mut previous_date = self.start;
+        let (mut m, mut y) = month_year_of(self.start);
+        let mut number = 0;
+        // the payments can't be shorter than the interest, but bad data shouldn't hang the UI
+        while remaining > 0 && number < self.term_months.max(1) * 2 {
+            number += 1;
+            let next = next_month(m, y);
+            m = next.0;
+            y = next.1;
+            let date = date_in_month(m, y, self.payment_day);
+
+            let interest = (f64::from(remaining) * rate).round() as i32;
+            let principal = (monthly_payment - interest).max(0).min(remaining);
+            remaining -= principal;
+            let extra = self
+                .extra_payments
+                .iter()
+                .filter(|extra_payment| {
+                    previous_date < extra_payment.date && extra_payment.date <= date
+                })
+                .map(|extra_payment| extra_payment.amount)
+                .sum::<i32>()
+                .min(remaining);
+            remaining -= extra;
+
+            schedule.push(ScheduledPayment {
+                number,
+                date,
+                payment: interest + principal,
+                interest,
+                principal,
+                extra,
+                remaining,
+            });
+            previous_date = date;
+        }
+        schedule
+    }
+
+    // the scheduled payments which are due and don't have their spending yet
+    pub fn due_payments(&self, today: NaiveDate) -> Vec<ScheduledPayment> {
+        self.schedule()
+            .into_iter()
+            .filter(|payment| {
+                payment.date <= today
+                    && self
+                        .paid_through
+                        .map_or(true, |paid_through| payment.date > paid_through)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loan(principal: i32, annual_rate: f64, term_months: u32) -> Loan {
+        Loan {
+            principal,
+            annual_rate,
+            term_months,
+            ..Loan::new(String::from("loan"), NaiveDate::from_ymd(2020, 1, 15))
+        }
+    }
+
+    #[test]
+    fn zero_rate_splits_the_principal_evenly() {
+        let schedule = loan(120_000, 0.0, 12).schedule();
+        assert_eq!(schedule.len(), 12);
+        for payment in &schedule {
+            assert_eq!(payment.interest, 0);
+            assert_eq!(payment.payment, 10_000);
+        }
+        assert_eq!(schedule[0].date, NaiveDate::from_ymd(2020, 2, 1));
+        assert_eq!(schedule[11].remaining, 0);
+    }
+
+    #[test]
+    fn final_payment_clears_the_balance() {
+        // the payment is rounded up, so the last one is smaller
+        let schedule = loan(100_000, 0.0, 3).schedule();
+        let payments = schedule
+            .iter()
+            .map(|payment| payment.payment)
+            .collect::<Vec<_>>();
+        assert_eq!(payments, vec![33_334, 33_334, 33_332]);
+        assert_eq!(schedule[2].remaining, 0);
+
+        let loan = loan(100_000, 12.0, 12);
+        let schedule = loan.schedule();
+        assert_eq!(schedule.len(), 12);
+        let last = &schedule[11];
+        assert_eq!(last.remaining, 0);
+        assert_eq!(last.principal, schedule[10].remaining);
+        assert!(last.payment <= loan.monthly_payment());
+        assert_eq!(
+            schedule
+                .iter()
+                .map(|payment| payment.principal)
+                .sum::<i32>(),
+            100_000
+        );
+    }
+
+    #[test]
+    fn extra_payment_shortens_the_term() {
+        let mut loan = loan(120_000, 0.0, 12);
+        loan.extra_payments.push(ExtraPayment {
+            date: NaiveDate::from_ymd(2020, 2, 15),
+            amount: 30_000,
+        });
+        let schedule = loan.schedule();
+        assert_eq!(schedule.len(), 9);
+        assert_eq!(schedule[1].extra, 30_000);
+        assert_eq!(schedule[1].remaining, 70_000);
+        assert_eq!(schedule[8].payment, 10_000);
+        assert_eq!(schedule[8].remaining, 0);
+    }
+}
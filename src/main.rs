@@ -1,8 +1,24 @@
+mod alerts;
+mod bills;
+mod classifier;
 mod config;
 mod data;
 mod data_to_model;
+mod duplicates;
 mod file_loader;
+mod forecast;
+mod goals;
+mod loans;
 mod main_window;
+mod net_worth;
+mod periods;
+mod placeholders;
+mod reconciliation;
+mod reimbursements;
+mod reports;
+mod rules;
+mod search;
+mod shared_expenses;
 mod translation_provider;
 
 use relm::Widget;
@@ -11,14 +27,37 @@ pub struct MoneyzModel {
     file_loader: file_loader::FileLoader,
     relm: relm::Relm<main_window::MainWindow>,
     spending_category_combox_box: Option<gtk::CellRendererCombo>,
-    spending_day_combox_box: Option<gtk::CellRendererCombo>,
+    budget_category_parent_combo_box: Option<gtk::CellRendererCombo>,
+    spending_paid_by_combo_box: Option<gtk::CellRendererCombo>,
+    spending_reimbursement_combo_box: Option<gtk::CellRendererCombo>,
+    // between the spendings' store and their view
+    spendings_sort: Option<gtk::TreeModelSort>,
 
-    selected_month: data::Month,
-    selected_year: data::Year,
-    today: data::Day,
+    selected_period: periods::BudgetPeriod,
+    today: chrono::NaiveDate,
 
     budget_categories: data::BudgetCategories,
+    budget_category_parents: data::BudgetCategoryParents,
+    payees: data::Payees,
+    members: data::Members,
+    // shared by the completions of the name cells
+    payee_completion_model: gtk::ListStore,
+    rules: rules::Rules,
+    goals: goals::Goals,
+    loans: loans::Loans,
+    bills: bills::Bills,
+    // trained on every month of the data directory
+    category_classifier: classifier::CategoryClassifier,
+    // every month, read when the period is opened and then only for the months which are saved
+    all_monthly_budgets: Vec<(data::Month, data::Year, data::MonthlyBudget)>,
     monthly_budget: data::MonthlyBudget,
+    // the months around the selected period (without its spendings), where duplicates are also
+    // looked for
+    adjacent_monthly_budgets: Vec<(data::Month, data::Year, data::MonthlyBudget)>,
+    // one entry per spending of monthly_budget
+    duplicates: Vec<Option<duplicates::DuplicateOf>>,
+    // the alerts of the selected period, as of the last check
+    alerts: Vec<alerts::Alert>,
 
     translation_provider: translation_provider::TranslationProvider,
     config: config::Config,
@@ -27,15 +66,45 @@ pub struct MoneyzModel {
 
 #[derive(relm_derive::Msg, Debug)]
 pub enum MoneyzMsg {
-    ChangeSelectedDate,
+    PreviousPeriodClicked,
+    NextPeriodClicked,
+    BudgetPeriodClicked,
     LanguageChanged,
+    BudgetingModeToggled,
+    MoveMoneyClicked,
+    SplitSpendingClicked,
+    SpendingTagsCellChanged(gtk::TreePath, String),
+    SpendingNoteCellChanged(gtk::TreePath, String),
+    SpendingPaidByCellChanged(gtk::TreePath, String),
+    SpendingShareCellChanged(gtk::TreePath, String),
+    SpendingReimbursementCellChanged(gtk::TreePath, String),
+    TagsReportClicked,
+    PayeesClicked,
+    RulesClicked,
+    ReapplyRulesClicked,
+    AcceptSuggestionsClicked,
+    MergeDuplicatesClicked,
+    DismissDuplicatesClicked,
+    ReconcileClicked,
+    GoalsClicked,
+    ForecastClicked,
+    NetWorthClicked,
+    LoansClicked,
+    MembersClicked,
+    SettleUpClicked,
+    ReimbursementsClicked,
+    AlertsClicked,
+    BillsClicked,
+    SearchActivated,
+    SpendingsFilterChanged,
     BudgetCategoriesDeleteKeyPressed,
     SpendingsDeleteKeyPressed,
     SpendingCategoryCellChanged(gtk::TreePath, String),
     SpendingNameCellChanged(gtk::TreePath, String),
     SpendingAmountCellChanged(gtk::TreePath, String),
-    SpendingDayCellChanged(gtk::TreePath, String),
+    SpendingDateCellChanged(gtk::TreePath, String),
     CategoryNameChanged(gtk::TreePath, String),
+    CategoryParentChanged(gtk::TreePath, String),
     BudgetAmountChanged(gtk::TreePath, String),
     Quit,
 }
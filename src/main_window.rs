@@ -1,42 +1,85 @@
+use crate::alerts::{evaluate, notify};
+use crate::bills::{Bill, BillStatus, Bills, BILL_REPEATS};
+use crate::classifier::CategoryClassifier;
+use crate::config::{AlertSettings, BudgetingMode};
 use crate::data::{
-    BudgetAmount, BudgetCategory, BudgetCategoryId, Day, MoneyAmount, Month, Spending, Year,
+    date_in_month, month_year_of, next_month, normalize_tag, parse_aliases, parse_tags,
+    previous_month, BudgetAmount, BudgetCategory, BudgetCategoryId, ClearedStatus, Day,
+    EnvelopeTransfer, MoneyAmount, Month, PayeeId, ReimbursementStatus, Spending, SpendingId,
+    SpendingSplit, Year, DATE_FORMAT, REIMBURSEMENT_STATUSES,
 };
 use crate::data_to_model::{
-    add_default_budget_category, add_default_spending,
-    get_model_from_budget_categories_and_monthly_budget, get_spendings_model,
-    list_model_from_month_year, BudgetCategoriesListStoreIds, BudgetCategoryComboBoxIds,
-    SpendingsGtkModelIds,
+    add_default_budget_category, add_default_spending, alert_message, bill_repeat_name,
+    fill_payee_completion_model, format_amount, get_alerts_model, get_bill_occurrences_model,
+    get_bill_repeats_model, get_bills_model, get_forecast_model, get_goals_model,
+    get_holding_kinds_model, get_holding_names_model, get_holdings_model, get_loan_schedule_model,
+    get_loans_model, get_member_balances_model, get_members_model,
+    get_model_from_budget_categories_and_monthly_budget, get_net_worth_model,
+    get_optional_budget_category_model, get_optional_member_model, get_payees_model,
+    get_reconcile_model, get_reimbursement_statuses_model, get_reimbursements_model,
+    get_rules_model, get_search_results_model, get_settlements_model, get_sorted_spendings_model,
+    get_spendings_model, get_tag_totals_model, get_tagged_spendings_model, get_valuations_model,
+    holding_kind_name, reimbursement_status_name, AlertsListStoreIds, BillOccurrencesListStoreIds,
+    BillsListStoreIds, BudgetCategoriesTreeStoreIds, BudgetCategoryComboBoxIds,
+    ForecastListStoreIds, GoalsListStoreIds, HoldingsListStoreIds, LoanScheduleListStoreIds,
+    LoansListStoreIds, MemberBalancesListStoreIds, MembersListStoreIds, NetWorthListStoreIds,
+    PayeesListStoreIds, ReconcileListStoreIds, ReimbursementsListStoreIds, RulesListStoreIds,
+    SearchResultsListStoreIds, SettlementsListStoreIds, SpendingsGtkModelIds,
+    TagTotalsListStoreIds, TaggedSpendingsListStoreIds, ValuationsListStoreIds,
+    NO_BUDGET_CATEGORY_ID, NO_PAYEE_ID,
 };
+use crate::duplicates::{find_duplicates, merge_into, DuplicateOf};
 use crate::file_loader::FileLoader;
+use crate::forecast::forecast;
+use crate::goals::{parse_deadline, Goals, SavingsGoal};
+use crate::loans::{ExtraPayment, Loan, Loans};
+use crate::net_worth::{Holding, HoldingId, HoldingKind, Valuation, Valuations, HOLDING_KINDS};
+use crate::periods::BudgetPeriodKind;
+use crate::reconciliation::{cleared_balance, reconcile};
+use crate::reimbursements::{is_outstanding, is_unlinked_income, link, next_link_id, to_csv};
+use crate::reports::{collect_tagged_spendings, total_for_tag};
+use crate::rules::{Rule, Rules};
+use crate::search::{search, SearchQuery};
+use crate::shared_expenses::{balances, parse_share_rule, settle_up};
 use crate::translation_provider::TranslationProvider;
 use crate::{MoneyzModel, MoneyzMsg};
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
 use gdk;
 use gtk::Orientation::{Horizontal, Vertical};
 use gtk::*;
 use log::debug;
 use relm::{connect, connect_stream, Widget};
 use relm_derive::widget;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 const MARGIN_LEFT: i32 = 15;
 const MARGIN_BETWEEN: i32 = 3;
 
-const FIRST_YEAR: u32 = 2000;
-const LAST_YEAR: u32 = 2100;
+// ids of the budget period kinds in the settings dialog
+const PERIOD_CALENDAR_MONTH_ID: &str = "calendar_month";
+const PERIOD_MONTH_STARTING_ON_ID: &str = "month_starting_on";
+const PERIOD_WEEKLY_ID: &str = "weekly";
+const PERIOD_BI_WEEKLY_ID: &str = "bi_weekly";
 
 #[widget]
 impl Widget for MainWindow {
     fn model(relm: &relm::Relm<Self>, file_loader: FileLoader) -> MoneyzModel {
         let local: chrono::DateTime<chrono::Local> = chrono::Local::now();
-        let today = Day(local.date().day() as i32);
-        let current_month = local.date().month() - 1; // chrono starts counting at 1
-        let current_year = local.date().year();
-        let selected_month: Month = num_traits::FromPrimitive::from_u32(current_month).unwrap();
-        let selected_year = Year(current_year as u32);
+        let today = local.date().naive_local();
 
         // no need to initialize the model there, because when we set the initial date (to
         // today's), the callback already takes care of loading the model
         let budget_categories = Default::default();
+        let budget_category_parents = Default::default();
+        let payees = Default::default();
+        let members = Default::default();
+        let payee_completion_model = gtk::ListStore::new(&[String::static_type()]);
+        let rules = Default::default();
+        let goals = Default::default();
+        let loans = Default::default();
+        let bills = Default::default();
+        let category_classifier = Default::default();
         let monthly_budget = Default::default();
         let config = file_loader
             .load_config()
@@ -44,27 +87,42 @@ impl Widget for MainWindow {
         let translation_provider = TranslationProvider::get_provider(&config.language)
             .expect("Language ID does not exist!");
         let language_list = TranslationProvider::get_language_list();
+        let selected_period = config.budget_period.period_containing(today);
 
         MoneyzModel {
             file_loader,
             relm: relm.clone(),
             spending_category_combox_box: None,
-            spending_day_combox_box: None,
-            selected_month,
-            selected_year,
+            budget_category_parent_combo_box: None,
+            spending_paid_by_combo_box: None,
+            spending_reimbursement_combo_box: None,
+            spendings_sort: None,
+            selected_period,
             today,
             budget_categories,
+            budget_category_parents,
+            payees,
+            members,
+            payee_completion_model,
+            rules,
+            goals,
+            loans,
+            bills,
+            category_classifier,
             monthly_budget,
+            all_monthly_budgets: Vec::new(),
+            adjacent_monthly_budgets: Vec::new(),
+            duplicates: Vec::new(),
+            alerts: Vec::new(),
             translation_provider,
             config,
             language_list,
         }
     }
 
-    fn initialize_budget_categories_headers(&self) {
-        use BudgetCategoriesListStoreIds::*;
+    fn initialize_budget_categories_headers(&mut self) {
+        use BudgetCategoriesTreeStoreIds::*;
         let col = gtk::TreeViewColumn::new();
-        col.set_title(&self.model.translation_provider.budget_category_header());
         let cell = gtk::CellRendererText::new();
         cell.set_property_editable(true);
         col.pack_start(&cell, true);
@@ -78,7 +136,6 @@ impl Widget for MainWindow {
         });
 
         let col = gtk::TreeViewColumn::new();
-        col.set_title(&self.model.translation_provider.budget_amount_header());
         let cell = gtk::CellRendererText::new();
         cell.set_property_editable(true);
         col.pack_start(&cell, true);
@@ -93,7 +150,6 @@ impl Widget for MainWindow {
         });
 
         let col = gtk::TreeViewColumn::new();
-        col.set_title(&self.model.translation_provider.budget_balance_header());
         let cell = gtk::CellRendererText::new();
         cell.set_property_editable(false);
         col.pack_start(&cell, true);
@@ -101,6 +157,28 @@ impl Widget for MainWindow {
         col.add_attribute(&cell, "background", BalanceBackgroundColor.into());
         self.budget_categories_tree_view.append_column(&col);
 
+        let col = gtk::TreeViewColumn::new();
+        let cell = gtk::CellRendererCombo::new();
+        let parent_model = get_optional_budget_category_model(
+            &self.model.file_loader.load_budget_categories().unwrap(),
+            &self.model.translation_provider,
+        );
+        let tree_model = parent_model.upcast::<gtk::TreeModel>();
+        cell.set_property_model(Some(&tree_model));
+        cell.set_property_editable(true);
+        cell.set_property_has_entry(false);
+        cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", ParentName.into());
+        col.add_attribute(&cell, "background", ParentNameBackgroundColor.into());
+        let relm = self.model.relm.clone();
+        cell.connect_edited(move |_, path, value| {
+            relm.stream()
+                .emit(MoneyzMsg::CategoryParentChanged(path, value.to_owned()));
+        });
+        self.budget_categories_tree_view.append_column(&col);
+        self.model.budget_category_parent_combo_box = Some(cell);
+
         // handle keypressed
         let relm = self.model.relm.clone();
         self.budget_categories_tree_view
@@ -124,11 +202,13 @@ impl Widget for MainWindow {
     fn initialize_spendings_tree_view_headers(&mut self) {
         use SpendingsGtkModelIds::*;
         let col = gtk::TreeViewColumn::new();
-        col.set_title(&self.model.translation_provider.spending_name_header());
         let cell = gtk::CellRendererText::new();
         cell.set_property_editable(true);
         col.pack_start(&cell, true);
         col.add_attribute(&cell, "text", Name.into());
+        col.set_sort_column_id(Name.into());
+        // reconciled spendings are locked
+        col.add_attribute(&cell, "editable", Editable.into());
         col.add_attribute(&cell, "background", NameBackgroundColor.into());
         self.spendings_tree_view.append_column(&col);
         let relm = self.model.relm.clone();
@@ -136,14 +216,18 @@ impl Widget for MainWindow {
             relm.stream()
                 .emit(MoneyzMsg::SpendingNameCellChanged(path, value.to_owned()));
         });
+        // autocomplete the known payees
+        let payee_completion_model = self.model.payee_completion_model.clone();
+        cell.connect_editing_started(move |_, editable, _| {
+            if let Some(entry) = editable.downcast_ref::<gtk::Entry>() {
+                let completion = gtk::EntryCompletion::new();
+                completion.set_model(Some(&payee_completion_model));
+                completion.set_text_column(0);
+                entry.set_completion(Some(&completion));
+            }
+        });
 
         let col = gtk::TreeViewColumn::new();
-        col.set_title(
-            &self
-                .model
-                .translation_provider
-                .spending_budget_category_header(),
-        );
         let cell = gtk::CellRendererCombo::new();
         let category_model: gtk::ListStore = self
             .model
@@ -158,6 +242,8 @@ impl Widget for MainWindow {
         cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
         col.pack_start(&cell, true);
         col.add_attribute(&cell, "text", CategoryName.into());
+        col.set_sort_column_id(CategoryName.into());
+        col.add_attribute(&cell, "editable", Editable.into());
         col.add_attribute(&cell, "background", CategoryNameBackgroundColor.into());
         let relm = self.model.relm.clone();
         cell.connect_edited(move |_, path, value| {
@@ -170,11 +256,12 @@ impl Widget for MainWindow {
         self.model.spending_category_combox_box = Some(cell);
 
         let col = gtk::TreeViewColumn::new();
-        col.set_title(&self.model.translation_provider.spending_amount_header());
         let cell = gtk::CellRendererText::new();
         cell.set_property_editable(true);
         col.pack_start(&cell, true);
         col.add_attribute(&cell, "text", Amount.into());
+        col.set_sort_column_id(Amount.into());
+        col.add_attribute(&cell, "editable", Editable.into());
         col.add_attribute(&cell, "background", AmountBackgroundColor.into());
         self.spendings_tree_view.append_column(&col);
         let relm = self.model.relm.clone();
@@ -184,47 +271,139 @@ impl Widget for MainWindow {
         });
 
         let col = gtk::TreeViewColumn::new();
-        col.set_title(&self.model.translation_provider.spending_day_header());
+        let cell = gtk::CellRendererText::new();
+        cell.set_property_editable(true);
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Date.into());
+        col.set_sort_column_id(Date.into());
+        col.add_attribute(&cell, "editable", Editable.into());
+        col.add_attribute(&cell, "background", DateBackgroundColor.into());
+        col.add_attribute(&cell, "visible", SpendingCellsVisible.into());
+        let relm = self.model.relm.clone();
+        cell.connect_edited(move |_, path, value| {
+            relm.stream()
+                .emit(MoneyzMsg::SpendingDateCellChanged(path, value.to_owned()));
+        });
+        // the date can be typed in, or picked from a calendar
+        cell.connect_editing_started(|_, editable, _| {
+            let entry = if let Some(entry) = editable.downcast_ref::<gtk::Entry>() {
+                entry.clone()
+            } else {
+                return;
+            };
+            let calendar = gtk::Calendar::new();
+            if let Some(date) = entry
+                .get_text()
+                .and_then(|text| NaiveDate::parse_from_str(&text, DATE_FORMAT).ok())
+            {
+                calendar.select_month(date.month0(), date.year() as u32);
+                calendar.select_day(date.day());
+            }
+            let popover = gtk::Popover::new(Some(&entry));
+            popover.add(&calendar);
+            calendar.connect_day_selected_double_click(move |calendar| {
+                entry.set_text(&calendar_date(calendar).format(DATE_FORMAT).to_string());
+                // ends the edition, which emits the "edited" signal
+                entry.activate();
+            });
+            popover.show_all();
+        });
+        self.spendings_tree_view.append_column(&col);
+
+        let col = gtk::TreeViewColumn::new();
+        let cell = gtk::CellRendererText::new();
+        cell.set_property_editable(true);
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Tags.into());
+        col.add_attribute(&cell, "editable", Editable.into());
+        col.add_attribute(&cell, "visible", SpendingCellsVisible.into());
+        self.spendings_tree_view.append_column(&col);
+        let relm = self.model.relm.clone();
+        cell.connect_edited(move |_, path, value| {
+            relm.stream()
+                .emit(MoneyzMsg::SpendingTagsCellChanged(path, value.to_owned()));
+        });
+
+        let col = gtk::TreeViewColumn::new();
+        let cell = gtk::CellRendererText::new();
+        cell.set_property_editable(true);
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Note.into());
+        col.add_attribute(&cell, "editable", Editable.into());
+        col.add_attribute(&cell, "visible", SpendingCellsVisible.into());
+        self.spendings_tree_view.append_column(&col);
+        let relm = self.model.relm.clone();
+        cell.connect_edited(move |_, path, value| {
+            relm.stream()
+                .emit(MoneyzMsg::SpendingNoteCellChanged(path, value.to_owned()));
+        });
+
+        let col = gtk::TreeViewColumn::new();
+        // the model is set once the members are loaded
         let cell = gtk::CellRendererCombo::new();
-        let day_model =
-            list_model_from_month_year(self.model.selected_month, self.model.selected_year);
-        let tree_model = day_model.upcast::<gtk::TreeModel>();
-        cell.set_property_model(Some(&tree_model));
         cell.set_property_editable(true);
         cell.set_property_has_entry(false);
-        cell.set_property_text_column(0);
+        cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
         col.pack_start(&cell, true);
-        col.add_attribute(&cell, "text", Day.into());
-        col.add_attribute(&cell, "background", DayBackgroundColor.into());
+        col.add_attribute(&cell, "text", PaidBy.into());
+        col.add_attribute(&cell, "editable", Editable.into());
+        col.add_attribute(&cell, "visible", SpendingCellsVisible.into());
+        self.spendings_tree_view.append_column(&col);
         let relm = self.model.relm.clone();
         cell.connect_edited(move |_, path, value| {
             relm.stream()
-                .emit(MoneyzMsg::SpendingDayCellChanged(path, value.to_owned()));
+                .emit(MoneyzMsg::SpendingPaidByCellChanged(path, value.to_owned()));
         });
+        self.model.spending_paid_by_combo_box = Some(cell);
+
+        let col = gtk::TreeViewColumn::new();
+        let cell = gtk::CellRendererText::new();
+        cell.set_property_editable(true);
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Share.into());
+        col.add_attribute(&cell, "editable", Editable.into());
+        col.add_attribute(&cell, "visible", SpendingCellsVisible.into());
         self.spendings_tree_view.append_column(&col);
-        self.model.spending_day_combox_box = Some(cell);
-        self.spendings_tree_view
-            .get_selection()
-            .set_mode(gtk::SelectionMode::Multiple);
-    }
+        let relm = self.model.relm.clone();
+        cell.connect_edited(move |_, path, value| {
+            relm.stream()
+                .emit(MoneyzMsg::SpendingShareCellChanged(path, value.to_owned()));
+        });
+
+        let col = gtk::TreeViewColumn::new();
+        let cell = gtk::CellRendererCombo::new();
+        cell.set_property_editable(true);
+        cell.set_property_has_entry(false);
+        cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+        // the model is set with the labels, as they are translated
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Reimbursement.into());
+        col.add_attribute(&cell, "editable", Editable.into());
+        col.add_attribute(&cell, "visible", SpendingCellsVisible.into());
+        self.spendings_tree_view.append_column(&col);
+        let relm = self.model.relm.clone();
+        cell.connect_edited(move |_, path, value| {
+            relm.stream()
+                .emit(MoneyzMsg::SpendingReimbursementCellChanged(
+                    path,
+                    value.to_owned(),
+                ));
+        });
+        self.model.spending_reimbursement_combo_box = Some(cell);
 
-    fn initialize_month_year_combo_boxes(&self) {
+        let col = gtk::TreeViewColumn::new();
         let cell = gtk::CellRendererText::new();
-        let month_model = self.create_and_fill_month_model();
-        self.month_combo_box.set_model(Some(&month_model));
-        self.month_combo_box.pack_start(&cell, true);
-        self.month_combo_box.add_attribute(&cell, "text", 0);
-        self.month_combo_box
-            .set_active(Some(self.model.selected_month as u32));
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Suggestion.into());
+        self.spendings_tree_view.append_column(&col);
 
+        let col = gtk::TreeViewColumn::new();
         let cell = gtk::CellRendererText::new();
-        let year_model = self.create_and_fill_year_model();
-        self.year_combo_box.set_model(Some(&year_model));
-        self.year_combo_box.pack_start(&cell, true);
-        self.year_combo_box.add_attribute(&cell, "text", 0);
-        self.year_combo_box
-            .set_active(Some(self.model.selected_year.0 - FIRST_YEAR));
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", Status.into());
+        self.spendings_tree_view.append_column(&col);
 
+        // handle keypressed
         let relm = self.model.relm.clone();
         self.spendings_tree_view
             .connect_key_press_event(move |_, event| {
@@ -238,6 +417,50 @@ impl Widget for MainWindow {
                     _ => Inhibit(false),
                 }
             });
+        self.spendings_tree_view
+            .get_selection()
+            .set_mode(gtk::SelectionMode::Multiple);
+    }
+
+    // the titles of the columns, in the order they were appended
+    fn translate_tree_views_headers(&self) {
+        let translation_provider = &self.model.translation_provider;
+        let budget_categories_titles = [
+            translation_provider.budget_category_header(),
+            translation_provider.budget_amount_header(),
+            translation_provider.budget_balance_header(),
+            translation_provider.budget_parent_header(),
+        ];
+        for (col, title) in self
+            .budget_categories_tree_view
+            .get_columns()
+            .iter()
+            .zip(&budget_categories_titles)
+        {
+            col.set_title(title);
+        }
+
+        let spendings_titles = [
+            translation_provider.spending_name_header(),
+            translation_provider.spending_budget_category_header(),
+            translation_provider.spending_amount_header(),
+            translation_provider.spending_date_header(),
+            translation_provider.spending_tags_header(),
+            translation_provider.spending_note_header(),
+            translation_provider.spending_paid_by_header(),
+            translation_provider.spending_share_header(),
+            translation_provider.spending_reimbursement_header(),
+            translation_provider.spending_suggestion_header(),
+            translation_provider.spending_status_header(),
+        ];
+        for (col, title) in self
+            .spendings_tree_view
+            .get_columns()
+            .iter()
+            .zip(&spendings_titles)
+        {
+            col.set_title(title);
+        }
     }
 
     fn initialize_language_combo_box(&self) {
@@ -251,6 +474,21 @@ impl Widget for MainWindow {
             .set_active_id(Some(&self.model.config.language));
     }
 
+    fn initialize_budgeting_mode_widgets(&self) {
+        // those are only shown in zero-based mode, so show_all must not touch them
+        self.ready_to_assign_label.set_no_show_all(true);
+        self.move_money_button.set_no_show_all(true);
+        self.zero_based_check_button
+            .set_active(self.model.config.budgeting_mode == BudgetingMode::ZeroBased);
+        self.update_budgeting_mode_widgets_visibility();
+    }
+
+    fn update_budgeting_mode_widgets_visibility(&self) {
+        let zero_based = self.model.config.budgeting_mode == BudgetingMode::ZeroBased;
+        self.ready_to_assign_label.set_visible(zero_based);
+        self.move_money_button.set_visible(zero_based);
+    }
+
     fn on_budget_amount_changed(&mut self, path: gtk::TreePath, value: String) {
         debug!("Budget amount modified; new value: {}", value);
         let amount = if let Some(amount) =
@@ -263,40 +501,23 @@ impl Widget for MainWindow {
         };
         debug!("Parsed amount: {}", amount.to_i32());
 
-        let budget_category_row = path.get_indices()[0] as usize;
-        match self
-            .model
-            .budget_categories
-            .0
-            .iter_mut()
-            .nth(budget_category_row)
-        {
-            // we changed the amount for a known budget_category
-            Some((id, _)) => {
-                self.model
-                    .monthly_budget
-                    .budgets
-                    .insert(*id, BudgetAmount(amount.to_i32()));
-            }
-            None => (),
+        // we can only change the amount of a known budget_category
+        if let Some(id) = self.budget_category_id_from_path(&path) {
+            self.model
+                .monthly_budget
+                .budgets
+                .insert(id, BudgetAmount(amount.to_i32()));
         }
 
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.save_selected_period();
 
         // needed to update the UI - probably much slower than setting the value directly!
         self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_total_label_from_moneyz_model();
     }
 
     fn on_category_name_changed(&mut self, path: gtk::TreePath, value: String) {
         debug!("Category name has been changed: {}", value);
-        let budget_category_row = path.get_indices()[0] as usize;
         for budget_category in self.model.budget_categories.0.values() {
             if budget_category.0 == value {
                 debug!("Selected category name already exists!");
@@ -304,16 +525,16 @@ impl Widget for MainWindow {
             }
         }
 
-        match &mut self
-            .model
-            .budget_categories
-            .0
-            .iter_mut()
-            .nth(budget_category_row)
-        {
+        let budget_category_id = self
+            .budget_category_id_from_path(&path)
+            .filter(|id| self.model.budget_categories.0.contains_key(id));
+        match budget_category_id {
             // budget_category exists - update it
-            Some((id, name)) => {
-                name.0 = value.clone();
+            Some(id) => {
+                self.model
+                    .budget_categories
+                    .0
+                    .insert(id, BudgetCategory(value.clone()));
                 for spending in &mut self.model.monthly_budget.spendings.0 {
                     if spending.budget_category_id.0 == id.0 {
                         spending.budget_category_name = BudgetCategory(value.to_string());
@@ -343,25 +564,50 @@ impl Widget for MainWindow {
             .file_loader
             .save_budget_categories(&self.model.budget_categories)
             .unwrap();
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.save_selected_period();
 
         // update the gtk model
         self.update_budget_categories_gtk_model_from_moneyz_model();
         self.update_monthly_budget_gtk_model_from_moneyz_model();
-        let category_model: gtk::ListStore = (&self.model.budget_categories).into();
-        let tree_model = category_model.upcast::<gtk::TreeModel>();
+        self.update_budget_category_combo_boxes_models();
+    }
+
+    fn on_category_parent_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!("Category parent has been changed: {}", value);
+        let budget_category_id = if let Some(id) = self.budget_category_id_from_path(&path) {
+            id
+        } else {
+            debug!("The default category cannot have a parent");
+            return;
+        };
+
+        // anything not matching a category (i.e. the "no parent" entry) makes it a top-level one
+        let parent_id = self
+            .model
+            .budget_categories
+            .0
+            .iter()
+            .find(|(_, name)| name.0 == value)
+            .map(|(id, _)| *id);
+        if let Some(parent_id) = parent_id {
+            if self
+                .model
+                .budget_category_parents
+                .is_descendant_of(parent_id, budget_category_id)
+            {
+                debug!("A category cannot be moved under one of its own sub-categories!");
+                return;
+            }
+        }
         self.model
-            .spending_category_combox_box
-            .as_ref()
-            .unwrap()
-            .set_property_model(Some(&tree_model));
+            .budget_category_parents
+            .set_parent(budget_category_id, parent_id);
+
+        self.model
+            .file_loader
+            .save_budget_category_parents(&self.model.budget_category_parents)
+            .unwrap();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
     }
 
     fn on_spending_amount_cell_changed(&mut self, path: gtk::TreePath, value: String) {
@@ -376,159 +622,181 @@ impl Widget for MainWindow {
         };
         debug!("Parsed amount: {}", amount.to_i32());
 
-        let spending_category_row = path.get_indices()[0] as usize;
-        match &mut self
-            .model
-            .monthly_budget
-            .spendings
-            .0
-            .get_mut(spending_category_row)
-        {
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split line - update it
+            (Some(spending), Some(split_row)) => {
+                if let Some(split) = spending.splits.get_mut(split_row) {
+                    split.amount = amount;
+                }
+            }
             // spending - update it
-            Some(spending) => {
+            (Some(spending), None) => {
                 spending.amount = amount;
+                // amount based rules could not match when the spending was created
+                if spending.budget_category_id == BudgetCategoryId(NO_BUDGET_CATEGORY_ID) {
+                    self.model
+                        .rules
+                        .apply(spending, &self.model.budget_categories);
+                }
             }
             // spending does NOT exist - we modified the default entry and have to create
             // and new one
-            None => {
-                // TODO: use translation_provider
-                let name = self.model.translation_provider.spending_name_placeholder();
-                let budget_category_id = BudgetCategoryId(u32::max_value());
-                let budget_category_name = BudgetCategory(
-                    self.model
-                        .translation_provider
-                        .spending_category_name_placeholder(),
-                );
-                let day = self.model.today;
-
-                self.model.monthly_budget.spendings.0.push(Spending {
-                    name,
-                    budget_category_id,
-                    budget_category_name,
-                    amount,
-                    day,
-                });
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.amount = amount;
+                self.push_new_spending(spending);
             }
         }
 
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.save_selected_period();
 
         self.update_budget_categories_gtk_model_from_moneyz_model();
         self.update_monthly_total_label_from_moneyz_model();
         self.update_monthly_budget_gtk_model_from_moneyz_model();
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.save_selected_period();
     }
 
     fn on_spending_name_cell_changed(&mut self, path: gtk::TreePath, value: String) {
         debug!("Spending name has been updated; new value: {}", value);
 
-        let spending_category_row = path.get_indices()[0] as usize;
-        match &mut self
-            .model
-            .monthly_budget
-            .spendings
-            .0
-            .get_mut(spending_category_row)
-        {
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+
+        // known payees are always written the same way, whatever alias was typed
+        let payee = match split_row {
+            Some(_) => None,
+            None => self.model.payees.find(&value),
+        };
+        let payee_budget_category = payee
+            .and_then(|payee| payee.default_budget_category_id)
+            .and_then(|id| {
+                self.model
+                    .budget_categories
+                    .0
+                    .get(&id)
+                    .map(|name| (id, name.clone()))
+            });
+        let value = payee.map_or(value, |payee| payee.name.clone());
+
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split line - the name column holds its memo
+            (Some(spending), Some(split_row)) => {
+                if let Some(split) = spending.splits.get_mut(split_row) {
+                    split.memo = value;
+                }
+            }
             // spending exists - update it
-            Some(spending) => {
+            (Some(spending), None) => {
                 spending.name = value;
+                // an uncategorized spending gets the payee's default category, unless a rule
+                // says otherwise
+                if spending.budget_category_id == BudgetCategoryId(NO_BUDGET_CATEGORY_ID) {
+                    if let Some((id, name)) = payee_budget_category {
+                        spending.budget_category_id = id;
+                        spending.budget_category_name = name;
+                    }
+                    self.model
+                        .rules
+                        .apply(spending, &self.model.budget_categories);
+                }
             }
             // spending does NOT exist - we modified the default entry and have to create
             // and new one
-            None => {
-                // TODO: use translation_provider
-                let budget_category_id = BudgetCategoryId(u32::max_value());
-                let budget_category_name = BudgetCategory(
-                    self.model
-                        .translation_provider
-                        .spending_category_name_placeholder(),
-                );
-                let day = self.model.today;
-                let amount = Default::default();
-
-                self.model.monthly_budget.spendings.0.push(Spending {
-                    name: value,
-                    budget_category_id,
-                    budget_category_name,
-                    amount,
-                    day,
-                });
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.name = value;
+                if let Some((id, name)) = payee_budget_category {
+                    spending.budget_category_id = id;
+                    spending.budget_category_name = name;
+                }
+                self.model
+                    .rules
+                    .apply(&mut spending, &self.model.budget_categories);
+                self.model.monthly_budget.spendings.0.push(spending);
             }
         }
         self.update_monthly_budget_gtk_model_from_moneyz_model();
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.save_selected_period();
     }
 
-    fn on_spending_day_cell_changed(&mut self, path: gtk::TreePath, value: String) {
-        debug!("Day cell modified; new value: {}", value);
-        let day = Day(value.parse::<i32>().unwrap());
+    fn on_spending_date_cell_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!("Date cell modified; new value: {}", value);
+        let date = if let Ok(date) = NaiveDate::parse_from_str(value.trim(), DATE_FORMAT) {
+            date
+        } else {
+            debug!("'{}' could NOT be parsed into a date", value);
+            return;
+        };
 
-        debug!("Parsed day: {}", day.0);
-        let spending_category_row = path.get_indices()[0] as usize;
-        match &mut self
-            .model
-            .monthly_budget
-            .spendings
-            .0
-            .get_mut(spending_category_row)
-        {
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        let spending_idx = match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split lines share the date of their spending
+            (Some(_), Some(_)) => return,
             // spending exists - update it
-            Some(spending) => {
-                spending.day = day;
+            (Some(spending), None) => {
+                spending.date = date;
+                spending_category_row
             }
             // spending does NOT exist - we modified the default entry and have to create
             // and new one
-            None => {
-                // TODO: use translation_provider
-                let budget_category_id = BudgetCategoryId(u32::max_value());
-                let budget_category_name = BudgetCategory(
-                    self.model
-                        .translation_provider
-                        .spending_category_name_placeholder(),
-                );
-                let name = self.model.translation_provider.spending_name_placeholder();
-                let amount = Default::default();
-
-                self.model.monthly_budget.spendings.0.push(Spending {
-                    name,
-                    budget_category_id,
-                    budget_category_name,
-                    amount,
-                    day,
-                });
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.date = date;
+                self.push_new_spending(spending)
             }
+        };
+
+        // the spending now belongs to another period, i.e. to the file of another month
+        if !self.model.selected_period.contains(date) {
+            let (m, y) = month_year_of(date);
+            debug!("Moving the spending to {}-{:02}", y.0, m.id());
+            let spending = self.model.monthly_budget.spendings.0.remove(spending_idx);
+            let mut other_monthly_budget =
+                self.model.file_loader.load_monthly_budget(m, y).unwrap();
+            other_monthly_budget.spendings.0.push(spending);
+            self.model
+                .file_loader
+                .save_monthly_budget(m, y, &other_monthly_budget)
+                .unwrap();
+            self.reload_months(&[(m, y)]);
+            self.load_adjacent_monthly_budgets();
+            self.update_monthly_total_label_from_moneyz_model();
         }
+
+        self.update_budget_categories_gtk_model_from_moneyz_model();
         self.update_monthly_budget_gtk_model_from_moneyz_model();
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.save_selected_period();
     }
 
     fn on_spending_category_cell_changed(&mut self, path: gtk::TreePath, value: String) {
@@ -541,50 +809,3292 @@ impl Widget for MainWindow {
             .map(|(id, _)| id)
             .expect("How come the ID wasn't in the budget_categories?");
 
-        let spending_category_row = path.get_indices()[0] as usize;
-        match &mut self
-            .model
-            .monthly_budget
-            .spendings
-            .0
-            .get_mut(spending_category_row)
-        {
-            // spending exists - update it
-            Some(spending) => {
-                spending.budget_category_id = *id;
-            }
-            // spending does NOT exist - we modified the default entry and have to create
-            // and new one
-            None => {
-                // TODO: use translation_provider
-                let budget_category_name = BudgetCategory(
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split line - update it
+            (Some(spending), Some(split_row)) => {
+                if let Some(split) = spending.splits.get_mut(split_row) {
+                    split.budget_category_id = *id;
+                    split.budget_category_name = BudgetCategory(value);
+                }
+            }
+            // spending exists - update it
+            (Some(spending), None) => {
+                spending.budget_category_id = *id;
+            }
+            // spending does NOT exist - we modified the default entry and have to create
+            // and new one
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.budget_category_id = *id;
+                self.push_new_spending(spending);
+            }
+        }
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn on_spending_tags_cell_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!("Spending tags have been updated; new value: {}", value);
+        let tags = parse_tags(&value);
+
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split lines share the tags of their spending
+            (Some(_), Some(_)) => return,
+            // spending exists - update it
+            (Some(spending), None) => {
+                spending.tags = tags;
+            }
+            // spending does NOT exist - we modified the default entry and have to create
+            // and new one
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.tags = tags;
+                self.push_new_spending(spending);
+            }
+        }
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn on_spending_note_cell_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!("Spending note has been updated; new value: {}", value);
+
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split lines have a memo instead
+            (Some(_), Some(_)) => return,
+            // spending exists - update it
+            (Some(spending), None) => {
+                spending.note = value;
+            }
+            // spending does NOT exist - we modified the default entry and have to create
+            // and new one
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.note = value;
+                self.push_new_spending(spending);
+            }
+        }
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    // the view doesn't let reconciled spendings be edited, this is the last line of defense
+    fn is_spending_locked(&self, spending_row: usize) -> bool {
+        let locked = self
+            .model
+            .monthly_budget
+            .spendings
+            .0
+            .get(spending_row)
+            .map_or(false, |spending| spending.is_reconciled());
+        if locked {
+            debug!("Spending {} is reconciled, ignoring the edit", spending_row);
+        }
+        locked
+    }
+
+    // today, or the first day of the selected period
+    fn default_spending_date(&self) -> NaiveDate {
+        if self.model.selected_period.contains(self.model.today) {
+            self.model.today
+        } else {
+            self.model.selected_period.start
+        }
+    }
+
+    fn save_selected_period(&mut self) {
+        self.model
+            .file_loader
+            .save_period_budget(&self.model.selected_period, &self.model.monthly_budget)
+            .unwrap();
+        // the contributions may have changed
+        let months = self.model.selected_period.months();
+        self.reload_months(&months);
+        self.update_goals_box();
+        self.check_alerts(true);
+    }
+
+    // the rules apply to a new spending whichever cell was edited first, e.g. for the amount
+    // based ones, unless its category was picked; the callers refresh the budget categories as
+    // the spending may have been categorized
+    fn push_new_spending(&mut self, mut spending: Spending) -> usize {
+        if spending.budget_category_id == BudgetCategoryId(NO_BUDGET_CATEGORY_ID) {
+            self.model
+                .rules
+                .apply(&mut spending, &self.model.budget_categories);
+        }
+        self.model.monthly_budget.spendings.0.push(spending);
+        self.model.monthly_budget.spendings.0.len() - 1
+    }
+
+    // what the default line of the spendings view turns into once it's edited
+    fn default_spending(&self) -> Spending {
+        // TODO: see comment on the Spendings declaration on why we use a dummy category
+        Spending {
+            id: SpendingId::new(),
+            name: self.model.translation_provider.spending_name_placeholder(),
+            budget_category_id: BudgetCategoryId(u32::max_value()),
+            budget_category_name: BudgetCategory(
+                self.model
+                    .translation_provider
+                    .spending_category_name_placeholder(),
+            ),
+            amount: Default::default(),
+            date: self.default_spending_date(),
+            legacy_day: None,
+            splits: Vec::new(),
+            tags: Vec::new(),
+            note: String::new(),
+            dismissed_duplicates: Vec::new(),
+            status: Default::default(),
+            paid_by: None,
+            share_rule: Default::default(),
+            reimbursement: None,
+            reimbursement_link: None,
+        }
+    }
+
+    fn on_spending_paid_by_cell_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!("Spending payer has been updated; new value: {}", value);
+
+        // anything not matching a member (i.e. the "nobody" entry) makes the spending personal
+        let paid_by = self.model.members.find(&value);
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split lines are paid along with their spending
+            (Some(_), Some(_)) => return,
+            // spending exists - update it
+            (Some(spending), None) => {
+                spending.paid_by = paid_by;
+            }
+            // spending does NOT exist - we modified the default entry and have to create
+            // and new one
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.paid_by = paid_by;
+                self.push_new_spending(spending);
+            }
+        }
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn on_spending_share_cell_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!("Spending share has been updated; new value: {}", value);
+
+        let share_rule = if let Some(share_rule) = parse_share_rule(
+            &value,
+            &self.model.members,
+            &self.model.translation_provider.share_equal(),
+            &self.model.translation_provider.decimal_separator(),
+        ) {
+            share_rule
+        } else {
+            debug!("Invalid share, ignoring it");
+            return;
+        };
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // only the spendings which have a payer show their share
+            (Some(spending), None) if spending.paid_by.is_some() => {
+                spending.share_rule = share_rule;
+            }
+            _ => return,
+        }
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn on_spending_reimbursement_cell_changed(&mut self, path: gtk::TreePath, value: String) {
+        debug!(
+            "Spending reimbursement has been updated; new value: {}",
+            value
+        );
+
+        // anything not matching a status (i.e. the "not reimbursable" entry) clears it
+        let reimbursement = REIMBURSEMENT_STATUSES.iter().cloned().find(|status| {
+            reimbursement_status_name(*status, &self.model.translation_provider) == value
+        });
+        let (spending_category_row, split_row) = self.view_spending_and_split_rows(&path);
+        if self.is_spending_locked(spending_category_row) {
+            return;
+        }
+        match (
+            self.model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row),
+            split_row,
+        ) {
+            // split lines are claimed along with their spending
+            (Some(_), Some(_)) => return,
+            // spending exists - update it
+            (Some(spending), None) => {
+                spending.reimbursement = reimbursement;
+                // the link only makes sense for a reimbursed expense
+                if reimbursement != Some(ReimbursementStatus::Reimbursed) {
+                    spending.reimbursement_link = None;
+                }
+            }
+            // spending does NOT exist - we modified the default entry and have to create
+            // and new one
+            (None, _) => {
+                let mut spending = self.default_spending();
+                spending.reimbursement = reimbursement;
+                self.push_new_spending(spending);
+            }
+        }
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn update_member_combo_box_model(&self) {
+        let member_model =
+            get_optional_member_model(&self.model.members, &self.model.translation_provider);
+        let tree_model = member_model.upcast::<gtk::TreeModel>();
+        self.model
+            .spending_paid_by_combo_box
+            .as_ref()
+            .unwrap()
+            .set_property_model(Some(&tree_model));
+    }
+
+    fn update_reimbursement_combo_box_model(&self) {
+        let reimbursement_statuses_model =
+            get_reimbursement_statuses_model(&self.model.translation_provider);
+        let tree_model = reimbursement_statuses_model.upcast::<gtk::TreeModel>();
+        self.model
+            .spending_reimbursement_combo_box
+            .as_ref()
+            .unwrap()
+            .set_property_model(Some(&tree_model));
+    }
+
+    fn on_members_clicked(&mut self) {
+        let members = Rc::new(RefCell::new(std::mem::replace(
+            &mut self.model.members,
+            Default::default(),
+        )));
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.members_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let members_tree_view = gtk::TreeView::new();
+        members_tree_view
+            .get_selection()
+            .set_mode(gtk::SelectionMode::Multiple);
+        let refresh_members_tree_view: Rc<dyn Fn()> = {
+            let members = members.clone();
+            let members_tree_view = members_tree_view.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                members_tree_view.set_model(Some(&get_members_model(
+                    &members.borrow(),
+                    &translation_provider,
+                )));
+            })
+        };
+        refresh_members_tree_view();
+
+        let cell = append_editable_text_column(
+            &members_tree_view,
+            &translation_provider.member_name_header(),
+            MembersListStoreIds::Name.into(),
+            MembersListStoreIds::BackgroundColor.into(),
+        );
+        {
+            let members = members.clone();
+            let refresh_members_tree_view = refresh_members_tree_view.clone();
+            cell.connect_edited(move |_, path, value| {
+                {
+                    let mut members = members.borrow_mut();
+                    let member_id = members
+                        .0
+                        .keys()
+                        .nth(path.get_indices()[0] as usize)
+                        .cloned();
+                    match member_id {
+                        Some(member_id) => {
+                            if let Some(member) = members.0.get_mut(&member_id) {
+                                member.name = value.to_owned();
+                            }
+                        }
+                        // the default row - create a new member
+                        None => {
+                            members.add(value.to_owned());
+                        }
+                    }
+                }
+                refresh_members_tree_view();
+            });
+        }
+
+        {
+            let members = members.clone();
+            let refresh_members_tree_view = refresh_members_tree_view.clone();
+            members_tree_view.connect_key_press_event(move |members_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            members_tree_view.get_selection().get_selected_rows();
+                        {
+                            let mut members = members.borrow_mut();
+                            let selected_member_ids = selected_paths
+                                .iter()
+                                .filter_map(|path| {
+                                    members
+                                        .0
+                                        .keys()
+                                        .nth(path.get_indices()[0] as usize)
+                                        .cloned()
+                                })
+                                .collect::<Vec<_>>();
+                            for member_id in selected_member_ids {
+                                members.0.remove(&member_id);
+                            }
+                        }
+                        refresh_members_tree_view();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        dialog
+            .get_content_area()
+            .pack_start(&scrolled(&members_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model.members = members.replace(Default::default());
+        self.model
+            .file_loader
+            .save_members(&self.model.members)
+            .unwrap();
+        self.update_member_combo_box_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+    }
+
+    fn on_settle_up_clicked(&mut self) {
+        // every month is loaded, the date range picks the spendings
+        let monthly_budgets = Rc::new(self.model.file_loader.load_all_monthly_budgets().unwrap());
+        let members = Rc::new(self.model.members.clone());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.settle_up_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        // the selected period by default
+        let from_entry = gtk::Entry::new();
+        from_entry.set_text(
+            &self
+                .model
+                .selected_period
+                .start
+                .format(DATE_FORMAT)
+                .to_string(),
+        );
+        let to_entry = gtk::Entry::new();
+        to_entry.set_text(
+            &self
+                .model
+                .selected_period
+                .end
+                .format(DATE_FORMAT)
+                .to_string(),
+        );
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(MARGIN_BETWEEN as u32);
+        grid.set_column_spacing(MARGIN_BETWEEN as u32);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.settle_up_from())),
+            0,
+            0,
+            1,
+            1,
+        );
+        grid.attach(&from_entry, 1, 0, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.settle_up_to())),
+            0,
+            1,
+            1,
+            1,
+        );
+        grid.attach(&to_entry, 1, 1, 1, 1);
+
+        let balances_tree_view = gtk::TreeView::new();
+        append_text_column(
+            &balances_tree_view,
+            &translation_provider.member_name_header(),
+            MemberBalancesListStoreIds::Name.into(),
+            None,
+        );
+        append_text_column(
+            &balances_tree_view,
+            &translation_provider.settle_up_balance_header(),
+            MemberBalancesListStoreIds::Balance.into(),
+            Some(MemberBalancesListStoreIds::BalanceBackgroundColor.into()),
+        );
+        let settlements_tree_view = gtk::TreeView::new();
+        append_text_column(
+            &settlements_tree_view,
+            &translation_provider.settle_up_transfers_header(),
+            SettlementsListStoreIds::Transfer.into(),
+            None,
+        );
+
+        let refresh_settle_up: Rc<dyn Fn()> = {
+            let members = members.clone();
+            let from_entry = from_entry.clone();
+            let to_entry = to_entry.clone();
+            let balances_tree_view = balances_tree_view.clone();
+            let settlements_tree_view = settlements_tree_view.clone();
+            Rc::new(move || {
+                let parse_date = |entry: &gtk::Entry| {
+                    entry
+                        .get_text()
+                        .and_then(|text| NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok())
+                };
+                let (from, to) = match (parse_date(&from_entry), parse_date(&to_entry)) {
+                    (Some(from), Some(to)) => (from, to),
+                    _ => return,
+                };
+                let balances = balances(
+                    monthly_budgets
+                        .iter()
+                        .flat_map(|(_, _, monthly_budget)| &monthly_budget.spendings.0)
+                        .filter(|spending| from <= spending.date && spending.date <= to),
+                    &members,
+                );
+                balances_tree_view.set_model(Some(&get_member_balances_model(
+                    &balances,
+                    &members,
+                    &translation_provider,
+                )));
+                settlements_tree_view.set_model(Some(&get_settlements_model(
+                    &settle_up(&balances),
+                    &members,
+                    &translation_provider,
+                )));
+            })
+        };
+        for entry in &[&from_entry, &to_entry] {
+            let refresh_settle_up = refresh_settle_up.clone();
+            entry.connect_changed(move |_| refresh_settle_up());
+        }
+        refresh_settle_up();
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&grid, false, false, 0);
+        content_area.pack_start(&scrolled(&balances_tree_view), true, true, 0);
+        content_area.pack_start(&scrolled(&settlements_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+    }
+
+    fn on_reimbursements_clicked(&mut self) {
+        // same as the reconciliation: every month is loaded, the changed ones are saved at the end
+        let monthly_budgets = Rc::new(RefCell::new(
+            self.model.file_loader.load_all_monthly_budgets().unwrap(),
+        ));
+        let changed_months = Rc::new(RefCell::new(Vec::new()));
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.reimbursements_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let outstanding_tree_view = gtk::TreeView::new();
+        let incomes_tree_view = gtk::TreeView::new();
+        for tree_view in &[&outstanding_tree_view, &incomes_tree_view] {
+            use ReimbursementsListStoreIds::*;
+            append_text_column(
+                tree_view,
+                &translation_provider.spending_day_header(),
+                Date.into(),
+                None,
+            );
+            append_text_column(
+                tree_view,
+                &translation_provider.spending_name_header(),
+                Name.into(),
+                None,
+            );
+            append_text_column(
+                tree_view,
+                &translation_provider.spending_budget_category_header(),
+                CategoryName.into(),
+                None,
+            );
+            append_text_column(
+                tree_view,
+                &translation_provider.spending_amount_header(),
+                Amount.into(),
+                Some(AmountBackgroundColor.into()),
+            );
+        }
+        append_text_column(
+            &outstanding_tree_view,
+            &translation_provider.spending_reimbursement_header(),
+            ReimbursementsListStoreIds::Status.into(),
+            None,
+        );
+        let total_label = gtk::Label::new(None);
+
+        let refresh_reimbursements: Rc<dyn Fn()> = {
+            let monthly_budgets = monthly_budgets.clone();
+            let outstanding_tree_view = outstanding_tree_view.clone();
+            let incomes_tree_view = incomes_tree_view.clone();
+            let total_label = total_label.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                let monthly_budgets = monthly_budgets.borrow();
+                outstanding_tree_view.set_model(Some(&get_reimbursements_model(
+                    &monthly_budgets,
+                    is_outstanding,
+                    &translation_provider,
+                )));
+                incomes_tree_view.set_model(Some(&get_reimbursements_model(
+                    &monthly_budgets,
+                    is_unlinked_income,
+                    &translation_provider,
+                )));
+                let total = monthly_budgets
+                    .iter()
+                    .flat_map(|(_, _, monthly_budget)| &monthly_budget.spendings.0)
+                    .filter(|spending| is_outstanding(spending))
+                    .map(|spending| -spending.amount.to_i32())
+                    .sum::<i32>();
+                total_label.set_text(
+                    &translation_provider
+                        .reimbursements_total(format_amount(total, &translation_provider))
+                        .expect("Could not format the outstanding total!"),
+                );
+            })
+        };
+        refresh_reimbursements();
+
+        let mark_submitted_button = gtk::Button::new_with_label(
+            &translation_provider.reimbursement_mark_submitted_button(),
+        );
+        {
+            let monthly_budgets = monthly_budgets.clone();
+            let changed_months = changed_months.clone();
+            let outstanding_tree_view = outstanding_tree_view.clone();
+            let refresh_reimbursements = refresh_reimbursements.clone();
+            mark_submitted_button.connect_clicked(move |_| {
+                let (month_idx, spending_idx) =
+                    match selected_reimbursement_row(&outstanding_tree_view) {
+                        Some(indexes) => indexes,
+                        None => return,
+                    };
+                monthly_budgets.borrow_mut()[month_idx].2.spendings.0[spending_idx].reimbursement =
+                    Some(ReimbursementStatus::Submitted);
+                if !changed_months.borrow().contains(&month_idx) {
+                    changed_months.borrow_mut().push(month_idx);
+                }
+                refresh_reimbursements();
+            });
+        }
+
+        let link_button =
+            gtk::Button::new_with_label(&translation_provider.reimbursement_link_button());
+        {
+            let monthly_budgets = monthly_budgets.clone();
+            let changed_months = changed_months.clone();
+            let outstanding_tree_view = outstanding_tree_view.clone();
+            let incomes_tree_view = incomes_tree_view.clone();
+            let refresh_reimbursements = refresh_reimbursements.clone();
+            link_button.connect_clicked(move |_| {
+                let ((expense_month_idx, expense_idx), (income_month_idx, income_idx)) = match (
+                    selected_reimbursement_row(&outstanding_tree_view),
+                    selected_reimbursement_row(&incomes_tree_view),
+                ) {
+                    (Some(expense), Some(income)) => (expense, income),
+                    _ => return,
+                };
+                let mut monthly_budgets = monthly_budgets.borrow_mut();
+                let link_id = next_link_id(&monthly_budgets);
+                // both may be in the same month, the income is linked on a copy
+                let mut income =
+                    monthly_budgets[income_month_idx].2.spendings.0[income_idx].clone();
+                link(
+                    &mut monthly_budgets[expense_month_idx].2.spendings.0[expense_idx],
+                    &mut income,
+                    link_id,
+                );
+                monthly_budgets[income_month_idx].2.spendings.0[income_idx] = income;
+                let mut changed_months = changed_months.borrow_mut();
+                for month_idx in &[expense_month_idx, income_month_idx] {
+                    if !changed_months.contains(month_idx) {
+                        changed_months.push(*month_idx);
+                    }
+                }
+                drop(monthly_budgets);
+                refresh_reimbursements();
+            });
+        }
+
+        let export_button =
+            gtk::Button::new_with_label(&translation_provider.reimbursement_export_button());
+        {
+            let monthly_budgets = monthly_budgets.clone();
+            let translation_provider = translation_provider.clone();
+            let dialog = dialog.clone();
+            export_button.connect_clicked(move |_| {
+                let save_label = translation_provider.ok_button();
+                let cancel_label = translation_provider.cancel_button();
+                let file_chooser = gtk::FileChooserDialog::with_buttons(
+                    Some(&translation_provider.reimbursement_export_button()),
+                    Some(&dialog),
+                    gtk::FileChooserAction::Save,
+                    &[
+                        (save_label.as_str(), gtk::ResponseType::Ok),
+                        (cancel_label.as_str(), gtk::ResponseType::Cancel),
+                    ],
+                );
+                file_chooser.set_do_overwrite_confirmation(true);
+                file_chooser.set_current_name("reimbursements.csv");
+                let response = file_chooser.run();
+                let path = file_chooser.get_filename();
+                file_chooser.destroy();
+                let path = match (response, path) {
+                    (gtk::ResponseType::Ok, Some(path)) => path,
+                    _ => return,
+                };
+
+                let monthly_budgets = monthly_budgets.borrow();
+                let expenses = monthly_budgets
+                    .iter()
+                    .flat_map(|(_, _, monthly_budget)| &monthly_budget.spendings.0)
+                    .filter(|spending| is_outstanding(spending))
+                    .collect::<Vec<_>>();
+                let headers = [
+                    translation_provider.reimbursement_csv_date(),
+                    translation_provider.reimbursement_csv_name(),
+                    translation_provider.reimbursement_csv_category(),
+                    translation_provider.reimbursement_csv_amount(),
+                    translation_provider.reimbursement_csv_status(),
+                    translation_provider.reimbursement_csv_note(),
+                ];
+                let csv = to_csv(&headers, &expenses, |status| {
+                    reimbursement_status_name(status, &translation_provider)
+                });
+                match std::fs::write(&path, csv) {
+                    Ok(()) => debug!("Exported {} expenses to {:?}", expenses.len(), path),
+                    Err(e) => {
+                        let message_dialog = gtk::MessageDialog::new(
+                            Some(&dialog),
+                            gtk::DialogFlags::MODAL,
+                            gtk::MessageType::Error,
+                            gtk::ButtonsType::Close,
+                            &translation_provider
+                                .reimbursement_export_failed(e.to_string())
+                                .expect("Could not format the export error!"),
+                        );
+                        message_dialog.run();
+                        message_dialog.destroy();
+                    }
+                }
+            });
+        }
+
+        let buttons_box = gtk::Box::new(Horizontal, MARGIN_BETWEEN);
+        buttons_box.pack_start(&mark_submitted_button, false, false, 0);
+        buttons_box.pack_start(&link_button, false, false, 0);
+        buttons_box.pack_start(&export_button, false, false, 0);
+        buttons_box.pack_end(&total_label, false, false, 0);
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(
+            &gtk::Label::new(Some(
+                &translation_provider.reimbursements_outstanding_header(),
+            )),
+            false,
+            false,
+            0,
+        );
+        content_area.pack_start(&scrolled(&outstanding_tree_view), true, true, 0);
+        content_area.pack_start(
+            &gtk::Label::new(Some(&translation_provider.reimbursements_incomes_header())),
+            false,
+            false,
+            0,
+        );
+        content_area.pack_start(&scrolled(&incomes_tree_view), true, true, 0);
+        content_area.pack_start(&buttons_box, false, false, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        let monthly_budgets = monthly_budgets.borrow();
+        let mut months = Vec::new();
+        for month_idx in changed_months.borrow().iter() {
+            let (m, y, monthly_budget) = &monthly_budgets[*month_idx];
+            self.model
+                .file_loader
+                .save_monthly_budget(*m, *y, monthly_budget)
+                .unwrap();
+            months.push((*m, *y));
+        }
+        self.reload_months(&months);
+        self.update_goals_box();
+        self.model.monthly_budget = self
+            .model
+            .file_loader
+            .load_period_budget(&self.model.selected_period)
+            .unwrap();
+        // the linked incomes moved to their expense's category
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+    }
+
+    // the new alerts are also raised as desktop notifications
+    fn check_alerts(&mut self, notify_new: bool) {
+        let alerts = evaluate(
+            &self.model.config.alerts,
+            &self.model.budget_categories,
+            &self.model.monthly_budget,
+            self.model.config.budgeting_mode,
+        );
+        if notify_new && self.model.config.alerts.notifications {
+            let translation_provider = &self.model.translation_provider;
+            for alert in &alerts {
+                if self
+                    .model
+                    .alerts
+                    .iter()
+                    .any(|previous| previous.is_same_as(alert))
+                {
+                    continue;
+                }
+                notify(
+                    &translation_provider.alerts_button(),
+                    &alert_message(alert, &self.model.budget_categories, translation_provider),
+                );
+            }
+        }
+        self.model.alerts = alerts;
+
+        let label = if self.model.alerts.is_empty() {
+            self.model.translation_provider.alerts_button()
+        } else {
+            self.model
+                .translation_provider
+                .alerts_button_count(self.model.alerts.len().to_string())
+                .expect("Could not format the alerts button!")
+        };
+        self.alerts_button.set_label(&label);
+    }
+
+    fn on_alerts_clicked(&mut self) {
+        let translation_provider = &self.model.translation_provider;
+        let ok_label = translation_provider.ok_button();
+        let cancel_label = translation_provider.cancel_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.alerts_button()),
+            Some(&self.root()),
+            flags,
+            &[
+                (ok_label.as_str(), gtk::ResponseType::Ok),
+                (cancel_label.as_str(), gtk::ResponseType::Cancel),
+            ],
+        );
+
+        let alerts_tree_view = gtk::TreeView::new_with_model(&get_alerts_model(
+            &self.model.alerts,
+            &self.model.budget_categories,
+            translation_provider,
+        ));
+        append_text_column(
+            &alerts_tree_view,
+            &translation_provider.alert_header(),
+            AlertsListStoreIds::Message.into(),
+            Some(AlertsListStoreIds::BackgroundColor.into()),
+        );
+
+        let settings = self.model.config.alerts;
+        let notifications_check_button =
+            gtk::CheckButton::new_with_label(&translation_provider.alerts_notifications());
+        notifications_check_button.set_active(settings.notifications);
+        let nearly_spent_spin_button = gtk::SpinButton::new_with_range(1.0, 100.0, 1.0);
+        nearly_spent_spin_button.set_value(f64::from(settings.nearly_spent_percent));
+        let large_transaction_entry = gtk::Entry::new();
+        large_transaction_entry.set_text(&format_amount(
+            settings.large_transaction,
+            translation_provider,
+        ));
+
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(MARGIN_BETWEEN as u32);
+        grid.set_column_spacing(MARGIN_BETWEEN as u32);
+        grid.attach(&notifications_check_button, 0, 0, 2, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.alerts_nearly_spent_percent())),
+            0,
+            1,
+            1,
+            1,
+        );
+        grid.attach(&nearly_spent_spin_button, 1, 1, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.alerts_large_transaction())),
+            0,
+            2,
+            1,
+            1,
+        );
+        grid.attach(&large_transaction_entry, 1, 2, 1, 1);
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&scrolled(&alerts_tree_view), true, true, 0);
+        content_area.pack_start(&grid, false, false, 0);
+        dialog.show_all();
+        let response = dialog.run();
+
+        // an amount which can't be read keeps the previous threshold
+        let large_transaction = large_transaction_entry
+            .get_text()
+            .and_then(|text| {
+                MoneyAmount::from_string(text.trim(), &translation_provider.decimal_separator())
+            })
+            .map_or(settings.large_transaction, |amount| amount.to_i32().abs());
+        let new_settings = AlertSettings {
+            notifications: notifications_check_button.get_active(),
+            nearly_spent_percent: nearly_spent_spin_button.get_value_as_int() as u32,
+            large_transaction,
+        };
+        dialog.destroy();
+
+        if response != gtk::ResponseType::Ok || new_settings == settings {
+            return;
+        }
+        self.model.config.alerts = new_settings;
+        self.model
+            .file_loader
+            .save_config(&self.model.config)
+            .expect("Could not save configuration file!");
+        self.check_alerts(false);
+    }
+
+    fn on_search_activated(&mut self) {
+        // every month is loaded, the results point into them
+        let monthly_budgets = Rc::new(self.model.file_loader.load_all_monthly_budgets().unwrap());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+        // the result activated to jump to
+        let activated_result = Rc::new(Cell::new(None));
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.search_title()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let text_entry = gtk::Entry::new();
+        text_entry.set_text(
+            &self
+                .search_entry
+                .get_text()
+                .map_or_else(String::new, |text| text.to_string()),
+        );
+        let regex_check_button =
+            gtk::CheckButton::new_with_label(&translation_provider.search_regex());
+        // the empty id means any category
+        let category_combo_box = gtk::ComboBoxText::new();
+        category_combo_box.append(Some(""), &translation_provider.search_any_category());
+        for (id, budget_category) in &self.model.budget_categories.0 {
+            category_combo_box.append(Some(&id.0.to_string()), &budget_category.0);
+        }
+        category_combo_box.set_active_id(Some(""));
+        let min_amount_entry = gtk::Entry::new();
+        let max_amount_entry = gtk::Entry::new();
+        let from_entry = gtk::Entry::new();
+        let to_entry = gtk::Entry::new();
+        let status_label = gtk::Label::new(None);
+
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(MARGIN_BETWEEN as u32);
+        grid.set_column_spacing(MARGIN_BETWEEN as u32);
+        grid.attach(&text_entry, 0, 0, 3, 1);
+        grid.attach(&regex_check_button, 3, 0, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(
+                &translation_provider.spending_budget_category_header(),
+            )),
+            0,
+            1,
+            1,
+            1,
+        );
+        grid.attach(&category_combo_box, 1, 1, 3, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.search_min_amount())),
+            0,
+            2,
+            1,
+            1,
+        );
+        grid.attach(&min_amount_entry, 1, 2, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.search_max_amount())),
+            2,
+            2,
+            1,
+            1,
+        );
+        grid.attach(&max_amount_entry, 3, 2, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.search_from())),
+            0,
+            3,
+            1,
+            1,
+        );
+        grid.attach(&from_entry, 1, 3, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.search_to())),
+            2,
+            3,
+            1,
+            1,
+        );
+        grid.attach(&to_entry, 3, 3, 1, 1);
+
+        let results_tree_view = gtk::TreeView::new();
+        {
+            use SearchResultsListStoreIds::*;
+            append_text_column(
+                &results_tree_view,
+                &translation_provider.search_month_header(),
+                MonthName.into(),
+                None,
+            );
+            append_text_column(
+                &results_tree_view,
+                &translation_provider.spending_day_header(),
+                Date.into(),
+                None,
+            );
+            append_text_column(
+                &results_tree_view,
+                &translation_provider.spending_name_header(),
+                Name.into(),
+                None,
+            );
+            append_text_column(
+                &results_tree_view,
+                &translation_provider.spending_budget_category_header(),
+                CategoryName.into(),
+                None,
+            );
+            append_text_column(
+                &results_tree_view,
+                &translation_provider.spending_amount_header(),
+                Amount.into(),
+                Some(AmountBackgroundColor.into()),
+            );
+        }
+
+        let refresh_results: Rc<dyn Fn()> = {
+            let monthly_budgets = monthly_budgets.clone();
+            let translation_provider = translation_provider.clone();
+            let text_entry = text_entry.clone();
+            let regex_check_button = regex_check_button.clone();
+            let category_combo_box = category_combo_box.clone();
+            let min_amount_entry = min_amount_entry.clone();
+            let max_amount_entry = max_amount_entry.clone();
+            let from_entry = from_entry.clone();
+            let to_entry = to_entry.clone();
+            let status_label = status_label.clone();
+            let results_tree_view = results_tree_view.clone();
+            Rc::new(move || {
+                let decimal_separator = translation_provider.decimal_separator();
+                // an amount or a date which can't be read is ignored, as if it wasn't typed yet
+                let parse_amount = |entry: &gtk::Entry| {
+                    entry
+                        .get_text()
+                        .and_then(|text| MoneyAmount::from_string(text.trim(), &decimal_separator))
+                        .map(|amount| amount.to_i32().abs())
+                };
+                let parse_date = |entry: &gtk::Entry| {
+                    entry
+                        .get_text()
+                        .and_then(|text| NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok())
+                };
+                let query = SearchQuery {
+                    text: text_entry
+                        .get_text()
+                        .map_or_else(String::new, |text| text.to_string()),
+                    use_regex: regex_check_button.get_active(),
+                    budget_category_id: category_combo_box
+                        .get_active_id()
+                        .and_then(|id| id.parse::<u32>().ok())
+                        .map(BudgetCategoryId),
+                    min_amount: parse_amount(&min_amount_entry),
+                    max_amount: parse_amount(&max_amount_entry),
+                    from: parse_date(&from_entry),
+                    to: parse_date(&to_entry),
+                };
+                match search(&monthly_budgets, &query) {
+                    Ok(results) => {
+                        status_label.set_text(
+                            &translation_provider
+                                .search_result_count(results.len().to_string())
+                                .expect("Could not format the result count!"),
+                        );
+                        results_tree_view.set_model(Some(&get_search_results_model(
+                            &monthly_budgets,
+                            &results,
+                            &translation_provider,
+                        )));
+                    }
+                    // the previous results stay until the pattern is fixed
+                    Err(err) => status_label.set_text(&err.to_string()),
+                }
+            })
+        };
+        for entry in &[
+            &text_entry,
+            &min_amount_entry,
+            &max_amount_entry,
+            &from_entry,
+            &to_entry,
+        ] {
+            let refresh_results = refresh_results.clone();
+            entry.connect_changed(move |_| refresh_results());
+        }
+        {
+            let refresh_results = refresh_results.clone();
+            regex_check_button.connect_toggled(move |_| refresh_results());
+        }
+        {
+            let refresh_results = refresh_results.clone();
+            category_combo_box.connect_changed(move |_| refresh_results());
+        }
+        refresh_results();
+
+        {
+            let activated_result = activated_result.clone();
+            let dialog = dialog.clone();
+            results_tree_view.connect_row_activated(move |results_tree_view, path, _| {
+                use SearchResultsListStoreIds::*;
+                let model = results_tree_view.get_model().unwrap();
+                let iter = model.get_iter(path).unwrap();
+                let month_idx = model
+                    .get_value(&iter, MonthIndex.into())
+                    .get::<u32>()
+                    .unwrap() as usize;
+                let spending_idx = model
+                    .get_value(&iter, SpendingIndex.into())
+                    .get::<u32>()
+                    .unwrap() as usize;
+                activated_result.set(Some((month_idx, spending_idx)));
+                dialog.response(gtk::ResponseType::Close);
+            });
+        }
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&grid, false, false, 0);
+        content_area.pack_start(&status_label, false, false, 0);
+        content_area.pack_start(&scrolled(&results_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        if let Some((month_idx, spending_idx)) = activated_result.get() {
+            let spending = &monthly_budgets[month_idx].2.spendings.0[spending_idx];
+            self.jump_to_spending(spending.date, spending.id);
+        }
+    }
+
+    // the period of the spending is opened and its row selected
+    fn jump_to_spending(&mut self, date: NaiveDate, spending_id: SpendingId) {
+        self.model.selected_period = self.model.config.budget_period.period_containing(date);
+        self.load_selected_period();
+
+        // nothing is selected when the quick filter hides the spending
+        let sort = match &self.model.spendings_sort {
+            Some(sort) => sort,
+            None => return,
+        };
+        let iter = match sort.get_iter_first() {
+            Some(iter) => iter,
+            None => return,
+        };
+        loop {
+            if sort
+                .get_value(&iter, SpendingsGtkModelIds::Id.into())
+                .get::<u64>()
+                == Some(spending_id.0)
+            {
+                break;
+            }
+            if !sort.iter_next(&iter) {
+                return;
+            }
+        }
+        let path = match sort.get_path(&iter) {
+            Some(path) => path,
+            None => return,
+        };
+        let selection = self.spendings_tree_view.get_selection();
+        selection.unselect_all();
+        selection.select_path(&path);
+        self.spendings_tree_view.scroll_to_cell(
+            Some(&path),
+            None::<&gtk::TreeViewColumn>,
+            true,
+            0.5,
+            0.0,
+        );
+    }
+
+    fn on_tags_report_clicked(&mut self) {
+        let tagged_spendings = Rc::new(collect_tagged_spendings(
+            &self.model.file_loader.load_all_monthly_budgets().unwrap(),
+        ));
+        // the filter callback outlives this function, so it gets its own provider
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.tags_report_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        // the totals of every tag
+        let totals_tree_view = gtk::TreeView::new();
+        totals_tree_view.set_model(Some(&get_tag_totals_model(
+            &tagged_spendings,
+            &translation_provider,
+        )));
+        append_text_column(
+            &totals_tree_view,
+            &translation_provider.spending_tags_header(),
+            TagTotalsListStoreIds::Tag.into(),
+            None,
+        );
+        append_text_column(
+            &totals_tree_view,
+            &translation_provider.spending_amount_header(),
+            TagTotalsListStoreIds::Total.into(),
+            Some(TagTotalsListStoreIds::TotalBackgroundColor.into()),
+        );
+
+        // the spendings matching the filter
+        let filter_entry = gtk::Entry::new();
+        filter_entry.set_placeholder_text(Some(&translation_provider.tag_filter_placeholder()));
+        let spendings_tree_view = gtk::TreeView::new();
+        {
+            use TaggedSpendingsListStoreIds::*;
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_day_header(),
+                Date.into(),
+                None,
+            );
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_name_header(),
+                Name.into(),
+                None,
+            );
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_tags_header(),
+                Tags.into(),
+                None,
+            );
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_amount_header(),
+                Amount.into(),
+                Some(AmountBackgroundColor.into()),
+            );
+        }
+        let total_label = gtk::Label::new(None);
+        {
+            let spendings_tree_view = spendings_tree_view.clone();
+            let total_label = total_label.clone();
+            filter_entry.connect_changed(move |filter_entry| {
+                let tag = filter_entry
+                    .get_text()
+                    .map_or_else(String::new, |text| text.to_string());
+                spendings_tree_view.set_model(Some(&get_tagged_spendings_model(
+                    &tagged_spendings,
+                    &tag,
+                    &translation_provider,
+                )));
+                total_label.set_text(
+                    &translation_provider
+                        .tag_total(
+                            format!("#{}", normalize_tag(&tag)),
+                            format_amount(
+                                total_for_tag(&tagged_spendings, &tag),
+                                &translation_provider,
+                            ),
+                        )
+                        .expect("Could not format the tag total!"),
+                );
+            });
+        }
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&scrolled(&totals_tree_view), true, true, 0);
+        content_area.pack_start(&filter_entry, false, false, 0);
+        content_area.pack_start(&scrolled(&spendings_tree_view), true, true, 0);
+        content_area.pack_start(&total_label, false, false, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+    }
+
+    fn on_payees_clicked(&mut self) {
+        // the dialog's callbacks work on their own copies, which are written back once it's closed
+        let payees = Rc::new(RefCell::new(std::mem::replace(
+            &mut self.model.payees,
+            Default::default(),
+        )));
+        let budget_categories = Rc::new(self.model.budget_categories.clone());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.payees_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let payees_tree_view = gtk::TreeView::new();
+        payees_tree_view
+            .get_selection()
+            .set_mode(gtk::SelectionMode::Multiple);
+        let refresh_payees_tree_view: Rc<dyn Fn()> = {
+            let payees = payees.clone();
+            let payees_tree_view = payees_tree_view.clone();
+            let budget_categories = budget_categories.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                payees_tree_view.set_model(Some(&get_payees_model(
+                    &payees.borrow(),
+                    &budget_categories,
+                    &translation_provider,
+                )));
+            })
+        };
+        refresh_payees_tree_view();
+
+        let col = gtk::TreeViewColumn::new();
+        col.set_title(&translation_provider.payee_name_header());
+        let cell = gtk::CellRendererText::new();
+        cell.set_property_editable(true);
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", PayeesListStoreIds::Name.into());
+        col.add_attribute(
+            &cell,
+            "background",
+            PayeesListStoreIds::NameBackgroundColor.into(),
+        );
+        payees_tree_view.append_column(&col);
+        {
+            let payees = payees.clone();
+            let payees_tree_view = payees_tree_view.clone();
+            let refresh_payees_tree_view = refresh_payees_tree_view.clone();
+            cell.connect_edited(move |_, path, value| {
+                {
+                    let mut payees = payees.borrow_mut();
+                    match payee_id_from_path(&payees_tree_view, &path) {
+                        Some(id) => {
+                            if let Some(payee) = payees.0.get_mut(&id) {
+                                payee.name = value.to_owned();
+                            }
+                        }
+                        // the default row - create a new payee
+                        None => {
+                            payees.add(value.to_owned());
+                        }
+                    }
+                }
+                refresh_payees_tree_view();
+            });
+        }
+
+        let col = gtk::TreeViewColumn::new();
+        col.set_title(&translation_provider.payee_aliases_header());
+        let cell = gtk::CellRendererText::new();
+        cell.set_property_editable(true);
+        col.pack_start(&cell, true);
+        col.add_attribute(&cell, "text", PayeesListStoreIds::Aliases.into());
+        payees_tree_view.append_column(&col);
+        {
+            let payees = payees.clone();
+            let payees_tree_view = payees_tree_view.clone();
+            let refresh_payees_tree_view = refresh_payees_tree_view.clone();
+            cell.connect_edited(move |_, path, value| {
+                if let Some(id) = payee_id_from_path(&payees_tree_view, &path) {
+                    if let Some(payee) = payees.borrow_mut().0.get_mut(&id) {
+                        payee.aliases = parse_aliases(value);
+                    }
+                }
+                refresh_payees_tree_view();
+            });
+        }
+
+        let col = gtk::TreeViewColumn::new();
+        col.set_title(&translation_provider.payee_default_category_header());
+        let cell = gtk::CellRendererCombo::new();
+        let category_model =
+            get_optional_budget_category_model(&budget_categories, &translation_provider);
+        let tree_model = category_model.upcast::<gtk::TreeModel>();
+        cell.set_property_model(Some(&tree_model));
+        cell.set_property_editable(true);
+        cell.set_property_has_entry(false);
+        cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+        col.pack_start(&cell, true);
+        col.add_attribute(
+            &cell,
+            "text",
+            PayeesListStoreIds::DefaultBudgetCategoryName.into(),
+        );
+        payees_tree_view.append_column(&col);
+        {
+            let payees = payees.clone();
+            let payees_tree_view = payees_tree_view.clone();
+            let refresh_payees_tree_view = refresh_payees_tree_view.clone();
+            cell.connect_edited(move |_, path, value| {
+                // anything not matching a category (i.e. the "none" entry) removes the default
+                let budget_category_id = budget_categories
+                    .0
+                    .iter()
+                    .find(|(_, name)| name.0 == value)
+                    .map(|(id, _)| *id);
+                if let Some(id) = payee_id_from_path(&payees_tree_view, &path) {
+                    if let Some(payee) = payees.borrow_mut().0.get_mut(&id) {
+                        payee.default_budget_category_id = budget_category_id;
+                    }
+                }
+                refresh_payees_tree_view();
+            });
+        }
+
+        {
+            let payees = payees.clone();
+            let refresh_payees_tree_view = refresh_payees_tree_view.clone();
+            payees_tree_view.connect_key_press_event(move |payees_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            payees_tree_view.get_selection().get_selected_rows();
+                        {
+                            let mut payees = payees.borrow_mut();
+                            for path in selected_paths {
+                                if let Some(id) = payee_id_from_path(payees_tree_view, &path) {
+                                    payees.0.remove(&id);
+                                }
+                            }
+                        }
+                        refresh_payees_tree_view();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        dialog
+            .get_content_area()
+            .pack_start(&scrolled(&payees_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model.payees = payees.replace(Default::default());
+        self.model
+            .file_loader
+            .save_payees(&self.model.payees)
+            .unwrap();
+        fill_payee_completion_model(&self.model.payee_completion_model, &self.model.payees);
+    }
+
+    fn on_rules_clicked(&mut self) {
+        let rules = Rc::new(RefCell::new(std::mem::replace(
+            &mut self.model.rules,
+            Default::default(),
+        )));
+        let budget_categories = Rc::new(self.model.budget_categories.clone());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.rules_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let rules_tree_view = gtk::TreeView::new();
+        rules_tree_view
+            .get_selection()
+            .set_mode(gtk::SelectionMode::Multiple);
+        let refresh_rules_tree_view: Rc<dyn Fn()> = {
+            let rules = rules.clone();
+            let rules_tree_view = rules_tree_view.clone();
+            let budget_categories = budget_categories.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                // the priorities may have been edited
+                rules.borrow_mut().sort_by_priority();
+                rules_tree_view.set_model(Some(&get_rules_model(
+                    &rules.borrow(),
+                    &budget_categories,
+                    &translation_provider,
+                )));
+            })
+        };
+        refresh_rules_tree_view();
+        let new_rule = |rules: &Rules| Rule::new(rules.next_priority());
+
+        {
+            use RulesListStoreIds::*;
+            let cell = append_editable_text_column(
+                &rules_tree_view,
+                &translation_provider.rule_priority_header(),
+                Priority.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &rules,
+                &refresh_rules_tree_view,
+                new_rule,
+                |rule, value| {
+                    if let Ok(priority) = value.trim().parse::<i32>() {
+                        rule.priority = priority;
+                    }
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &rules_tree_view,
+                &translation_provider.rule_name_pattern_header(),
+                NamePattern.into(),
+                NamePatternBackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &rules,
+                &refresh_rules_tree_view,
+                new_rule,
+                |rule, value| {
+                    rule.name_pattern = if value.is_empty() {
+                        None
+                    } else {
+                        Some(value.to_owned())
+                    };
+                },
+            );
+
+            let decimal_separator = translation_provider.decimal_separator();
+            let cell = append_editable_text_column(
+                &rules_tree_view,
+                &translation_provider.rule_min_amount_header(),
+                MinAmount.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(&cell, &rules, &refresh_rules_tree_view, new_rule, {
+                let decimal_separator = decimal_separator.clone();
+                move |rule, value| {
+                    rule.min_amount = MoneyAmount::from_string(value.trim(), &decimal_separator)
+                        .map(|amount| amount.to_i32());
+                }
+            });
+
+            let cell = append_editable_text_column(
+                &rules_tree_view,
+                &translation_provider.rule_max_amount_header(),
+                MaxAmount.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &rules,
+                &refresh_rules_tree_view,
+                new_rule,
+                move |rule, value| {
+                    rule.max_amount = MoneyAmount::from_string(value.trim(), &decimal_separator)
+                        .map(|amount| amount.to_i32());
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &rules_tree_view,
+                &translation_provider.spending_day_header(),
+                Day.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &rules,
+                &refresh_rules_tree_view,
+                new_rule,
+                |rule, value| {
+                    rule.day = value
+                        .trim()
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|day| *day >= 1 && *day <= 31)
+                        .map(crate::data::Day);
+                },
+            );
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.spending_budget_category_header());
+            let cell = gtk::CellRendererCombo::new();
+            let category_model =
+                get_optional_budget_category_model(&budget_categories, &translation_provider);
+            let tree_model = category_model.upcast::<gtk::TreeModel>();
+            cell.set_property_model(Some(&tree_model));
+            cell.set_property_editable(true);
+            cell.set_property_has_entry(false);
+            cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "text", CategoryName.into());
+            col.add_attribute(&cell, "background", BackgroundColor.into());
+            rules_tree_view.append_column(&col);
+            connect_row_cell_edited(
+                &cell,
+                &rules,
+                &refresh_rules_tree_view,
+                new_rule,
+                move |rule, value| {
+                    rule.budget_category_id = budget_categories
+                        .0
+                        .iter()
+                        .find(|(_, name)| name.0 == value)
+                        .map(|(id, _)| *id);
+                },
+            );
+        }
+
+        {
+            let rules = rules.clone();
+            let refresh_rules_tree_view = refresh_rules_tree_view.clone();
+            rules_tree_view.connect_key_press_event(move |rules_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            rules_tree_view.get_selection().get_selected_rows();
+                        let mut selected_rule_rows = selected_paths
+                            .iter()
+                            .map(|path| path.get_indices()[0] as usize)
+                            .collect::<Vec<_>>();
+                        selected_rule_rows.sort_by(|a, b| b.cmp(a));
+                        {
+                            let mut rules = rules.borrow_mut();
+                            for selected_rule_row in selected_rule_rows {
+                                if selected_rule_row < rules.0.len() {
+                                    rules.0.remove(selected_rule_row);
+                                }
+                            }
+                        }
+                        refresh_rules_tree_view();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        dialog
+            .get_content_area()
+            .pack_start(&scrolled(&rules_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model.rules = rules.replace(Default::default());
+        self.model
+            .file_loader
+            .save_rules(&self.model.rules)
+            .unwrap();
+    }
+
+    // one progress bar per goal, with a warning when the contributions are behind schedule
+    fn update_goals_box(&self) {
+        for child in self.goals_box.get_children() {
+            self.goals_box.remove(&child);
+        }
+        if self.model.goals.0.is_empty() {
+            return;
+        }
+
+        let translation_provider = &self.model.translation_provider;
+        let today = self.model.today;
+        for goal in &self.model.goals.0 {
+            let saved = goal.saved(&self.model.all_monthly_budgets, today);
+            let label = gtk::Label::new(Some(
+                &translation_provider
+                    .goal_progress(
+                        goal.name.clone(),
+                        format_amount(saved, translation_provider),
+                        format_amount(goal.target, translation_provider),
+                        format_amount(
+                            goal.required_monthly_contribution(saved, today),
+                            translation_provider,
+                        ),
+                    )
+                    .expect("Could not format the goal progress!"),
+            ));
+            label.set_xalign(0.0);
+            self.goals_box.pack_start(&label, false, false, 0);
+
+            let progress_bar = gtk::ProgressBar::new();
+            progress_bar.set_fraction(goal.progress(saved));
+            progress_bar.set_margin_bottom(MARGIN_BETWEEN);
+            self.goals_box.pack_start(&progress_bar, false, false, 0);
+
+            if goal.is_behind_schedule(saved, today) {
+                let warning_label = gtk::Label::new(Some(
+                    &translation_provider
+                        .goal_behind_schedule(format_amount(
+                            goal.expected_saved(today),
+                            translation_provider,
+                        ))
+                        .expect("Could not format the goal warning!"),
+                ));
+                warning_label.set_xalign(0.0);
+                self.goals_box.pack_start(&warning_label, false, false, 0);
+            }
+        }
+        self.goals_box.show_all();
+    }
+
+    fn on_goals_clicked(&mut self) {
+        let goals = Rc::new(RefCell::new(std::mem::replace(
+            &mut self.model.goals,
+            Default::default(),
+        )));
+        let budget_categories = Rc::new(self.model.budget_categories.clone());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+        let today = self.model.today;
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.goals_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let goals_tree_view = gtk::TreeView::new();
+        goals_tree_view
+            .get_selection()
+            .set_mode(gtk::SelectionMode::Multiple);
+        let refresh_goals_tree_view: Rc<dyn Fn()> = {
+            let goals = goals.clone();
+            let goals_tree_view = goals_tree_view.clone();
+            let budget_categories = budget_categories.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                goals_tree_view.set_model(Some(&get_goals_model(
+                    &goals.borrow(),
+                    &budget_categories,
+                    &translation_provider,
+                )));
+            })
+        };
+        refresh_goals_tree_view();
+        let new_goal = move |_: &Goals| SavingsGoal::new(String::new(), today);
+
+        {
+            use GoalsListStoreIds::*;
+            let cell = append_editable_text_column(
+                &goals_tree_view,
+                &translation_provider.goal_name_header(),
+                Name.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &goals,
+                &refresh_goals_tree_view,
+                new_goal,
+                |goal, value| {
+                    goal.name = value.to_owned();
+                },
+            );
+
+            let decimal_separator = translation_provider.decimal_separator();
+            let cell = append_editable_text_column(
+                &goals_tree_view,
+                &translation_provider.goal_target_header(),
+                Target.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &goals,
+                &refresh_goals_tree_view,
+                new_goal,
+                move |goal, value| {
+                    if let Some(target) = MoneyAmount::from_string(value.trim(), &decimal_separator)
+                    {
+                        goal.target = target.to_i32();
+                    }
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &goals_tree_view,
+                &translation_provider.goal_deadline_header(),
+                Deadline.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &goals,
+                &refresh_goals_tree_view,
+                new_goal,
+                |goal, value| {
+                    if let Some(deadline) = parse_deadline(value) {
+                        goal.deadline = deadline;
+                    }
+                },
+            );
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.spending_budget_category_header());
+            let cell = gtk::CellRendererCombo::new();
+            let category_model =
+                get_optional_budget_category_model(&budget_categories, &translation_provider);
+            let tree_model = category_model.upcast::<gtk::TreeModel>();
+            cell.set_property_model(Some(&tree_model));
+            cell.set_property_editable(true);
+            cell.set_property_has_entry(false);
+            cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "text", CategoryName.into());
+            col.add_attribute(&cell, "background", BackgroundColor.into());
+            goals_tree_view.append_column(&col);
+            connect_row_cell_edited(
+                &cell,
+                &goals,
+                &refresh_goals_tree_view,
+                new_goal,
+                move |goal, value| {
+                    goal.budget_category_id = budget_categories
+                        .0
+                        .iter()
+                        .find(|(_, name)| name.0 == value)
+                        .map(|(id, _)| *id);
+                },
+            );
+        }
+
+        {
+            let goals = goals.clone();
+            let refresh_goals_tree_view = refresh_goals_tree_view.clone();
+            goals_tree_view.connect_key_press_event(move |goals_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            goals_tree_view.get_selection().get_selected_rows();
+                        let mut selected_goal_rows = selected_paths
+                            .iter()
+                            .map(|path| path.get_indices()[0] as usize)
+                            .collect::<Vec<_>>();
+                        selected_goal_rows.sort_by(|a, b| b.cmp(a));
+                        {
+                            let mut goals = goals.borrow_mut();
+                            for selected_goal_row in selected_goal_rows {
+                                if selected_goal_row < goals.0.len() {
+                                    goals.0.remove(selected_goal_row);
+                                }
+                            }
+                        }
+                        refresh_goals_tree_view();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        dialog
+            .get_content_area()
+            .pack_start(&scrolled(&goals_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model.goals = goals.replace(Default::default());
+        self.model
+            .file_loader
+            .save_goals(&self.model.goals)
+            .unwrap();
+        self.update_goals_box();
+    }
+
+    fn on_bills_clicked(&mut self) {
+        let bills = Rc::new(RefCell::new(std::mem::replace(
+            &mut self.model.bills,
+            Default::default(),
+        )));
+        // the bills marked as paid get their spending once the dialog is closed
+        let paid_bills = Rc::new(RefCell::new(Vec::new()));
+        let budget_categories = Rc::new(self.model.budget_categories.clone());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+        let today = self.model.today;
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.bills_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let bills_tree_view = gtk::TreeView::new();
+        bills_tree_view
+            .get_selection()
+            .set_mode(gtk::SelectionMode::Multiple);
+        let upcoming_tree_view = gtk::TreeView::new();
+        let calendar = gtk::Calendar::new();
+        let month_tree_view = gtk::TreeView::new();
+        for tree_view in &[&upcoming_tree_view, &month_tree_view] {
+            use BillOccurrencesListStoreIds::*;
+            append_text_column(
+                tree_view,
+                &translation_provider.bill_due_date_header(),
+                DueDate.into(),
+                None,
+            );
+            append_text_column(
+                tree_view,
+                &translation_provider.bill_name_header(),
+                Name.into(),
+                None,
+            );
+            append_text_column(
+                tree_view,
+                &translation_provider.bill_amount_header(),
+                Amount.into(),
+                None,
+            );
+            append_text_column(
+                tree_view,
+                &translation_provider.bill_status_header(),
+                Status.into(),
+                Some(StatusBackgroundColor.into()),
+            );
+        }
+
+        let refresh_bills: Rc<dyn Fn()> = {
+            let bills = bills.clone();
+            let bills_tree_view = bills_tree_view.clone();
+            let upcoming_tree_view = upcoming_tree_view.clone();
+            let calendar = calendar.clone();
+            let month_tree_view = month_tree_view.clone();
+            let budget_categories = budget_categories.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                let bills = bills.borrow();
+                bills_tree_view.set_model(Some(&get_bills_model(
+                    &bills,
+                    &budget_categories,
+                    &translation_provider,
+                )));
+                upcoming_tree_view.set_model(Some(&get_bill_occurrences_model(
+                    &bills.upcoming(today),
+                    &bills,
+                    &translation_provider,
+                )));
+
+                // the days with a bill left to pay are marked on the calendar
+                let (year, month, _) = calendar.get_date();
+                let (m, y) = month_year_of(NaiveDate::from_ymd(year as i32, month + 1, 1));
+                let occurrences =
+                    bills.occurrences(date_in_month(m, y, 1), date_in_month(m, y, 31), today);
+                calendar.clear_marks();
+                for occurrence in &occurrences {
+                    if occurrence.status != BillStatus::Paid {
+                        calendar.mark_day(occurrence.due_date.day());
+                    }
+                }
+                month_tree_view.set_model(Some(&get_bill_occurrences_model(
+                    &occurrences,
+                    &bills,
+                    &translation_provider,
+                )));
+            })
+        };
+        {
+            let refresh_bills = refresh_bills.clone();
+            calendar.connect_month_changed(move |_| refresh_bills());
+        }
+        refresh_bills();
+        let new_bill = move |_: &Bills| Bill::new(String::new(), today);
+
+        {
+            use BillsListStoreIds::*;
+            let cell = append_editable_text_column(
+                &bills_tree_view,
+                &translation_provider.bill_name_header(),
+                Name.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(&cell, &bills, &refresh_bills, new_bill, |bill, value| {
+                bill.name = value.to_owned();
+            });
+
+            let decimal_separator = translation_provider.decimal_separator();
+            let cell = append_editable_text_column(
+                &bills_tree_view,
+                &translation_provider.bill_amount_header(),
+                Amount.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &bills,
+                &refresh_bills,
+                new_bill,
+                move |bill, value| {
+                    // a bill is always paid, whichever sign was typed
+                    if let Some(amount) = MoneyAmount::from_string(value.trim(), &decimal_separator)
+                    {
+                        bill.amount = amount.to_i32().abs();
+                    }
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &bills_tree_view,
+                &translation_provider.bill_due_date_header(),
+                DueDate.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(&cell, &bills, &refresh_bills, new_bill, |bill, value| {
+                if let Ok(due_date) = NaiveDate::parse_from_str(value.trim(), DATE_FORMAT) {
+                    bill.due_date = due_date;
+                }
+            });
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.bill_repeat_header());
+            let cell = gtk::CellRendererCombo::new();
+            let repeats_model = get_bill_repeats_model(&translation_provider);
+            let tree_model = repeats_model.upcast::<gtk::TreeModel>();
+            cell.set_property_model(Some(&tree_model));
+            cell.set_property_editable(true);
+            cell.set_property_has_entry(false);
+            cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "text", Repeat.into());
+            col.add_attribute(&cell, "background", BackgroundColor.into());
+            bills_tree_view.append_column(&col);
+            {
+                let translation_provider = translation_provider.clone();
+                connect_row_cell_edited(
+                    &cell,
+                    &bills,
+                    &refresh_bills,
+                    new_bill,
+                    move |bill, value| {
+                        if let Some(repeat) = BILL_REPEATS.iter().find(|repeat| {
+                            bill_repeat_name(**repeat, &translation_provider) == value
+                        }) {
+                            bill.repeat = *repeat;
+                        }
+                    },
+                );
+            }
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.spending_budget_category_header());
+            let cell = gtk::CellRendererCombo::new();
+            let category_model =
+                get_optional_budget_category_model(&budget_categories, &translation_provider);
+            let tree_model = category_model.upcast::<gtk::TreeModel>();
+            cell.set_property_model(Some(&tree_model));
+            cell.set_property_editable(true);
+            cell.set_property_has_entry(false);
+            cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "text", CategoryName.into());
+            col.add_attribute(&cell, "background", BackgroundColor.into());
+            bills_tree_view.append_column(&col);
+            let budget_categories = budget_categories.clone();
+            connect_row_cell_edited(
+                &cell,
+                &bills,
+                &refresh_bills,
+                new_bill,
+                move |bill, value| {
+                    bill.budget_category_id = budget_categories
+                        .0
+                        .iter()
+                        .find(|(_, name)| name.0 == value)
+                        .map(|(id, _)| *id);
+                },
+            );
+        }
+
+        {
+            let bills = bills.clone();
+            let refresh_bills = refresh_bills.clone();
+            bills_tree_view.connect_key_press_event(move |bills_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            bills_tree_view.get_selection().get_selected_rows();
+                        let mut selected_bill_rows = selected_paths
+                            .iter()
+                            .map(|path| path.get_indices()[0] as usize)
+                            .collect::<Vec<_>>();
+                        selected_bill_rows.sort_by(|a, b| b.cmp(a));
+                        {
+                            let mut bills = bills.borrow_mut();
+                            for selected_bill_row in selected_bill_rows {
+                                if selected_bill_row < bills.0.len() {
+                                    bills.0.remove(selected_bill_row);
+                                }
+                            }
+                        }
+                        refresh_bills();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        let mark_paid_button =
+            gtk::Button::new_with_label(&translation_provider.bill_mark_paid_button());
+        {
+            let bills = bills.clone();
+            let paid_bills = paid_bills.clone();
+            let upcoming_tree_view = upcoming_tree_view.clone();
+            let refresh_bills = refresh_bills.clone();
+            mark_paid_button.connect_clicked(move |_| {
+                use BillOccurrencesListStoreIds::*;
+                let (model, iter) = match upcoming_tree_view.get_selection().get_selected() {
+                    Some(selected) => selected,
+                    None => return,
+                };
+                let bill_idx = model
+                    .get_value(&iter, BillIndex.into())
+                    .get::<u32>()
+                    .unwrap() as usize;
+                let due_date = model
+                    .get_value(&iter, DueDate.into())
+                    .get::<String>()
+                    .and_then(|text| NaiveDate::parse_from_str(&text, DATE_FORMAT).ok())
+                    .unwrap();
+                {
+                    let mut bills = bills.borrow_mut();
+                    let bill = &mut bills.0[bill_idx];
+                    bill.paid.push(due_date);
+                    paid_bills.borrow_mut().push(bill.clone());
+                }
+                refresh_bills();
+            });
+        }
+
+        let upcoming_box = gtk::Box::new(Vertical, MARGIN_BETWEEN);
+        upcoming_box.pack_start(
+            &gtk::Label::new(Some(&translation_provider.bills_upcoming_header())),
+            false,
+            false,
+            0,
+        );
+        upcoming_box.pack_start(&scrolled(&upcoming_tree_view), true, true, 0);
+        upcoming_box.pack_start(&mark_paid_button, false, false, 0);
+        let calendar_box = gtk::Box::new(Vertical, MARGIN_BETWEEN);
+        calendar_box.pack_start(&calendar, false, false, 0);
+        calendar_box.pack_start(&scrolled(&month_tree_view), true, true, 0);
+        let bottom_box = gtk::Box::new(Horizontal, MARGIN_BETWEEN);
+        bottom_box.pack_start(&upcoming_box, true, true, 0);
+        bottom_box.pack_start(&calendar_box, true, true, 0);
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&scrolled(&bills_tree_view), true, true, 0);
+        content_area.pack_start(&bottom_box, true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model.bills = bills.replace(Default::default());
+        self.model
+            .file_loader
+            .save_bills(&self.model.bills)
+            .unwrap();
+        let paid_bills = paid_bills.replace(Vec::new());
+        if !paid_bills.is_empty() {
+            self.create_bill_spendings(paid_bills);
+        }
+    }
+
+    // the bills are paid today, in the selected period when it's the current one
+    fn create_bill_spendings(&mut self, paid_bills: Vec<Bill>) {
+        let today = self.model.today;
+        let spendings = paid_bills
+            .into_iter()
+            .map(|bill| {
+                let mut spending = self.default_spending();
+                spending.name = bill.name;
+                spending.amount = MoneyAmount::from_i32(-bill.amount);
+                spending.date = today;
+                if let Some((id, name)) = bill.budget_category_id.and_then(|id| {
+                    self.model
+                        .budget_categories
+                        .0
+                        .get(&id)
+                        .map(|name| (id, name.clone()))
+                }) {
+                    spending.budget_category_id = id;
+                    spending.budget_category_name = name;
+                }
+                spending
+            })
+            .collect::<Vec<_>>();
+        debug!("Created the spendings of {} paid bills", spendings.len());
+
+        if self.model.selected_period.contains(today) {
+            self.model.monthly_budget.spendings.0.extend(spendings);
+            self.update_monthly_budget_gtk_model_from_moneyz_model();
+            self.update_budget_categories_gtk_model_from_moneyz_model();
+            self.save_selected_period();
+        } else {
+            let (m, y) = month_year_of(today);
+            let mut monthly_budget = self.model.file_loader.load_monthly_budget(m, y).unwrap();
+            monthly_budget.spendings.0.extend(spendings);
+            self.model
+                .file_loader
+                .save_monthly_budget(m, y, &monthly_budget)
+                .unwrap();
+            self.reload_months(&[(m, y)]);
+            self.update_goals_box();
+        }
+    }
+
+    fn on_forecast_clicked(&mut self) {
+        let monthly_budgets = Rc::new(self.model.file_loader.load_all_monthly_budgets().unwrap());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+        let today = self.model.today;
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.forecast_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let months_spin_button = gtk::SpinButton::new_with_range(1.0, 24.0, 1.0);
+        months_spin_button.set_value(6.0);
+        let months_box = gtk::Box::new(Horizontal, MARGIN_BETWEEN);
+        months_box.pack_start(
+            &gtk::Label::new(Some(&translation_provider.forecast_months())),
+            false,
+            false,
+            0,
+        );
+        months_box.pack_start(&months_spin_button, false, false, 0);
+        let lowest_point_label = gtk::Label::new(None);
+
+        let forecast_tree_view = gtk::TreeView::new();
+        {
+            use ForecastListStoreIds::*;
+            append_text_column(
+                &forecast_tree_view,
+                &translation_provider.forecast_month_header(),
+                MonthName.into(),
+                None,
+            );
+            append_text_column(
+                &forecast_tree_view,
+                &translation_provider.forecast_income_header(),
+                Income.into(),
+                None,
+            );
+            append_text_column(
+                &forecast_tree_view,
+                &translation_provider.forecast_expenses_header(),
+                Expenses.into(),
+                None,
+            );
+            append_text_column(
+                &forecast_tree_view,
+                &translation_provider.forecast_balance_header(),
+                Balance.into(),
+                Some(BalanceBackgroundColor.into()),
+            );
+        }
+
+        let refresh_forecast: Rc<dyn Fn()> = {
+            let forecast_tree_view = forecast_tree_view.clone();
+            let lowest_point_label = lowest_point_label.clone();
+            let months_spin_button = months_spin_button.clone();
+            Rc::new(move || {
+                let forecast = forecast(
+                    &monthly_budgets,
+                    today,
+                    months_spin_button.get_value_as_int() as usize,
+                );
+                forecast_tree_view
+                    .set_model(Some(&get_forecast_model(&forecast, &translation_provider)));
+                lowest_point_label.set_text(
+                    &translation_provider
+                        .forecast_lowest_point(
+                            format_amount(forecast.lowest_balance, &translation_provider),
+                            forecast.lowest_date.format(DATE_FORMAT).to_string(),
+                        )
+                        .expect("Could not format the lowest point!"),
+                );
+            })
+        };
+        {
+            let refresh_forecast = refresh_forecast.clone();
+            months_spin_button.connect_value_changed(move |_| refresh_forecast());
+        }
+        refresh_forecast();
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&months_box, false, false, 0);
+        content_area.pack_start(&scrolled(&forecast_tree_view), true, true, 0);
+        content_area.pack_start(&lowest_point_label, false, false, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+    }
+
+    fn on_net_worth_clicked(&mut self) {
+        let valuations = Rc::new(RefCell::new(
+            self.model.file_loader.load_valuations().unwrap(),
+        ));
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+        let today = self.model.today;
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.net_worth_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let holdings_tree_view = gtk::TreeView::new();
+        let valuations_tree_view = gtk::TreeView::new();
+        let net_worth_tree_view = gtk::TreeView::new();
+        for tree_view in &[&holdings_tree_view, &valuations_tree_view] {
+            tree_view
+                .get_selection()
+                .set_mode(gtk::SelectionMode::Multiple);
+        }
+        // the holdings can be renamed, so the combo box of the valuations is refreshed as well
+        let holding_cell = gtk::CellRendererCombo::new();
+        let refresh_tree_views: Rc<dyn Fn()> = {
+            let valuations = valuations.clone();
+            let holdings_tree_view = holdings_tree_view.clone();
+            let valuations_tree_view = valuations_tree_view.clone();
+            let net_worth_tree_view = net_worth_tree_view.clone();
+            let holding_cell = holding_cell.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                // the dates may have been edited
+                valuations.borrow_mut().sort_by_date();
+                let valuations = valuations.borrow();
+                holdings_tree_view.set_model(Some(&get_holdings_model(
+                    &valuations,
+                    &translation_provider,
+                )));
+                valuations_tree_view.set_model(Some(&get_valuations_model(
+                    &valuations,
+                    &translation_provider,
+                )));
+                net_worth_tree_view.set_model(Some(&get_net_worth_model(
+                    &valuations,
+                    today,
+                    &translation_provider,
+                )));
+                let holding_names_model =
+                    get_holding_names_model(&valuations).upcast::<gtk::TreeModel>();
+                holding_cell.set_property_model(Some(&holding_names_model));
+            })
+        };
+        refresh_tree_views();
+        let new_holding = |_: &Valuations| Holding {
+            name: String::new(),
+            kind: HoldingKind::Account,
+        };
+        let new_valuation = move |valuations: &Valuations| Valuation {
+            holding_id: valuations
+                .holdings
+                .keys()
+                .next()
+                .cloned()
+                .unwrap_or(HoldingId(0)),
+            date: today,
+            value: 0,
+        };
+
+        {
+            use HoldingsListStoreIds::*;
+            let cell = append_editable_text_column(
+                &holdings_tree_view,
+                &translation_provider.holding_name_header(),
+                Name.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &valuations,
+                &refresh_tree_views,
+                new_holding,
+                |holding, value| {
+                    holding.name = value.to_owned();
+                },
+            );
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.holding_kind_header());
+            let cell = gtk::CellRendererCombo::new();
+            let kinds_model = get_holding_kinds_model(&translation_provider);
+            let tree_model = kinds_model.upcast::<gtk::TreeModel>();
+            cell.set_property_model(Some(&tree_model));
+            cell.set_property_editable(true);
+            cell.set_property_has_entry(false);
+            cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "text", Kind.into());
+            col.add_attribute(&cell, "background", BackgroundColor.into());
+            holdings_tree_view.append_column(&col);
+            let translation_provider = translation_provider.clone();
+            connect_row_cell_edited(
+                &cell,
+                &valuations,
+                &refresh_tree_views,
+                new_holding,
+                move |holding, value| {
+                    if let Some(kind) = HOLDING_KINDS
+                        .iter()
+                        .find(|kind| holding_kind_name(**kind, &translation_provider) == value)
+                    {
+                        holding.kind = *kind;
+                    }
+                },
+            );
+        }
+
+        {
+            use ValuationsListStoreIds::*;
+            let cell = append_editable_text_column(
+                &valuations_tree_view,
+                &translation_provider.spending_date_header(),
+                Date.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &valuations,
+                &refresh_tree_views,
+                new_valuation,
+                |valuation, value| {
+                    if let Ok(date) = NaiveDate::parse_from_str(value.trim(), DATE_FORMAT) {
+                        valuation.date = date;
+                    }
+                },
+            );
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.valuation_holding_header());
+            holding_cell.set_property_editable(true);
+            holding_cell.set_property_has_entry(false);
+            holding_cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&holding_cell, true);
+            col.add_attribute(&holding_cell, "text", HoldingName.into());
+            col.add_attribute(&holding_cell, "background", BackgroundColor.into());
+            valuations_tree_view.append_column(&col);
+            connect_row_cell_edited(
+                &holding_cell,
+                &valuations,
+                &refresh_tree_views,
+                new_valuation,
+                {
+                    let valuations = valuations.clone();
+                    move |valuation, value| {
+                        if let Some(holding_id) = valuations
+                            .borrow()
+                            .holdings
+                            .iter()
+                            .find(|(_, holding)| holding.name == value)
+                            .map(|(id, _)| *id)
+                        {
+                            valuation.holding_id = holding_id;
+                        }
+                    }
+                },
+            );
+
+            let decimal_separator = translation_provider.decimal_separator();
+            let cell = append_editable_text_column(
+                &valuations_tree_view,
+                &translation_provider.valuation_value_header(),
+                Value.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &valuations,
+                &refresh_tree_views,
+                new_valuation,
+                move |valuation, value| {
+                    if let Some(amount) = MoneyAmount::from_string(value.trim(), &decimal_separator)
+                    {
+                        valuation.value = amount.to_i32();
+                    }
+                },
+            );
+        }
+
+        {
+            use NetWorthListStoreIds::*;
+            append_text_column(
+                &net_worth_tree_view,
+                &translation_provider.spending_date_header(),
+                Date.into(),
+                None,
+            );
+            append_text_column(
+                &net_worth_tree_view,
+                &translation_provider.net_worth_assets_header(),
+                Assets.into(),
+                None,
+            );
+            append_text_column(
+                &net_worth_tree_view,
+                &translation_provider.net_worth_liabilities_header(),
+                Liabilities.into(),
+                None,
+            );
+            append_text_column(
+                &net_worth_tree_view,
+                &translation_provider.net_worth_header(),
+                NetWorth.into(),
+                Some(NetWorthBackgroundColor.into()),
+            );
+        }
+
+        {
+            let valuations = valuations.clone();
+            let refresh_tree_views = refresh_tree_views.clone();
+            holdings_tree_view.connect_key_press_event(move |holdings_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            holdings_tree_view.get_selection().get_selected_rows();
+                        {
+                            let mut valuations = valuations.borrow_mut();
+                            let selected_holding_ids = selected_paths
+                                .iter()
+                                .filter_map(|path| {
+                                    valuations
+                                        .holdings
+                                        .keys()
+                                        .nth(path.get_indices()[0] as usize)
+                                        .cloned()
+                                })
+                                .collect::<Vec<_>>();
+                            for holding_id in selected_holding_ids {
+                                valuations.remove_holding(holding_id);
+                            }
+                        }
+                        refresh_tree_views();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+        {
+            let valuations = valuations.clone();
+            let refresh_tree_views = refresh_tree_views.clone();
+            valuations_tree_view.connect_key_press_event(move |valuations_tree_view, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        let (selected_paths, _) =
+                            valuations_tree_view.get_selection().get_selected_rows();
+                        let mut selected_valuation_rows = selected_paths
+                            .iter()
+                            .map(|path| path.get_indices()[0] as usize)
+                            .collect::<Vec<_>>();
+                        selected_valuation_rows.sort_by(|a, b| b.cmp(a));
+                        {
+                            let mut valuations = valuations.borrow_mut();
+                            for selected_valuation_row in selected_valuation_rows {
+                                if selected_valuation_row < valuations.valuations.len() {
+                                    valuations.valuations.remove(selected_valuation_row);
+                                }
+                            }
+                        }
+                        refresh_tree_views();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&scrolled(&holdings_tree_view), true, true, 0);
+        content_area.pack_start(&scrolled(&valuations_tree_view), true, true, 0);
+        content_area.pack_start(&scrolled(&net_worth_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model
+            .file_loader
+            .save_valuations(&valuations.borrow())
+            .unwrap();
+    }
+
+    fn on_loans_clicked(&mut self) {
+        let loans = Rc::new(RefCell::new(std::mem::replace(
+            &mut self.model.loans,
+            Default::default(),
+        )));
+        let budget_categories = Rc::new(self.model.budget_categories.clone());
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+        let today = self.model.today;
+
+        let close_label = translation_provider.close_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.loans_button()),
+            Some(&self.root()),
+            flags,
+            &[(close_label.as_str(), gtk::ResponseType::Close)],
+        );
+
+        let loans_tree_view = gtk::TreeView::new();
+        let schedule_tree_view = gtk::TreeView::new();
+        // the loan whose schedule is shown
+        let selected_loan_row = Rc::new(Cell::new(None::<usize>));
+        let refresh_schedule: Rc<dyn Fn()> = {
+            let loans = loans.clone();
+            let schedule_tree_view = schedule_tree_view.clone();
+            let selected_loan_row = selected_loan_row.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                let loans = loans.borrow();
+                match selected_loan_row.get().and_then(|row| loans.0.get(row)) {
+                    Some(loan) => schedule_tree_view
+                        .set_model(Some(&get_loan_schedule_model(loan, &translation_provider))),
+                    None => schedule_tree_view.set_model(None::<&gtk::ListStore>),
+                }
+            })
+        };
+        {
+            let selected_loan_row = selected_loan_row.clone();
+            let refresh_schedule = refresh_schedule.clone();
+            loans_tree_view
+                .get_selection()
+                .connect_changed(move |selection| {
+                    selected_loan_row.set(
+                        selection
+                            .get_selected_rows()
+                            .0
+                            .first()
+                            .map(|path| path.get_indices()[0] as usize),
+                    );
+                    refresh_schedule();
+                });
+        }
+        let refresh_loans_tree_view: Rc<dyn Fn()> = {
+            let loans = loans.clone();
+            let loans_tree_view = loans_tree_view.clone();
+            let selected_loan_row = selected_loan_row.clone();
+            let refresh_schedule = refresh_schedule.clone();
+            let budget_categories = budget_categories.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                // replacing the model clears the selection
+                let selected_row = selected_loan_row.get();
+                loans_tree_view.set_model(Some(&get_loans_model(
+                    &loans.borrow(),
+                    &budget_categories,
+                    &translation_provider,
+                )));
+                if let Some(path) =
+                    selected_row.and_then(|row| gtk::TreePath::new_from_string(&row.to_string()))
+                {
+                    loans_tree_view.get_selection().select_path(&path);
+                }
+                refresh_schedule();
+            })
+        };
+        refresh_loans_tree_view();
+        let new_loan = move |_: &Loans| Loan::new(String::new(), today);
+
+        {
+            use LoansListStoreIds::*;
+            let cell = append_editable_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_name_header(),
+                Name.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &loans,
+                &refresh_loans_tree_view,
+                new_loan,
+                |loan, value| {
+                    loan.name = value.to_owned();
+                },
+            );
+
+            let decimal_separator = translation_provider.decimal_separator();
+            let cell = append_editable_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_principal_header(),
+                Principal.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(&cell, &loans, &refresh_loans_tree_view, new_loan, {
+                let decimal_separator = decimal_separator.clone();
+                move |loan, value| {
+                    if let Some(amount) = MoneyAmount::from_string(value.trim(), &decimal_separator)
+                    {
+                        loan.principal = amount.to_i32();
+                    }
+                }
+            });
+
+            let cell = append_editable_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_rate_header(),
+                Rate.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &loans,
+                &refresh_loans_tree_view,
+                new_loan,
+                move |loan, value| {
+                    // the rate isn't an amount, but it's typed with the same separator
+                    if let Ok(rate) = value.trim().replace(&decimal_separator, ".").parse::<f64>() {
+                        if rate >= 0.0 {
+                            loan.annual_rate = rate;
+                        }
+                    }
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_term_header(),
+                Term.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &loans,
+                &refresh_loans_tree_view,
+                new_loan,
+                |loan, value| {
+                    if let Ok(term_months) = value.trim().parse::<u32>() {
+                        loan.term_months = term_months.max(1);
+                    }
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_start_header(),
+                Start.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &loans,
+                &refresh_loans_tree_view,
+                new_loan,
+                |loan, value| {
+                    if let Ok(start) = NaiveDate::parse_from_str(value.trim(), DATE_FORMAT) {
+                        loan.start = start;
+                    }
+                },
+            );
+
+            let cell = append_editable_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_payment_day_header(),
+                PaymentDay.into(),
+                BackgroundColor.into(),
+            );
+            connect_row_cell_edited(
+                &cell,
+                &loans,
+                &refresh_loans_tree_view,
+                new_loan,
+                |loan, value| {
+                    if let Ok(payment_day) = value.trim().parse::<u32>() {
+                        if payment_day >= 1 && payment_day <= 31 {
+                            loan.payment_day = payment_day;
+                        }
+                    }
+                },
+            );
+
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.spending_budget_category_header());
+            let cell = gtk::CellRendererCombo::new();
+            let category_model =
+                get_optional_budget_category_model(&budget_categories, &translation_provider);
+            let tree_model = category_model.upcast::<gtk::TreeModel>();
+            cell.set_property_model(Some(&tree_model));
+            cell.set_property_editable(true);
+            cell.set_property_has_entry(false);
+            cell.set_property_text_column(BudgetCategoryComboBoxIds::Name.into());
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "text", CategoryName.into());
+            col.add_attribute(&cell, "background", BackgroundColor.into());
+            loans_tree_view.append_column(&col);
+            connect_row_cell_edited(
+                &cell,
+                &loans,
+                &refresh_loans_tree_view,
+                new_loan,
+                move |loan, value| {
+                    loan.budget_category_id = budget_categories
+                        .0
+                        .iter()
+                        .find(|(_, name)| name.0 == value)
+                        .map(|(id, _)| *id);
+                },
+            );
+
+            append_text_column(
+                &loans_tree_view,
+                &translation_provider.loan_monthly_payment_header(),
+                MonthlyPayment.into(),
+                Some(BackgroundColor.into()),
+            );
+        }
+
+        {
+            use LoanScheduleListStoreIds::*;
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.loan_schedule_number_header(),
+                Number.into(),
+                None,
+            );
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.spending_date_header(),
+                Date.into(),
+                None,
+            );
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.loan_schedule_payment_header(),
+                Payment.into(),
+                None,
+            );
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.loan_schedule_interest_header(),
+                Interest.into(),
+                None,
+            );
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.loan_schedule_principal_header(),
+                Principal.into(),
+                None,
+            );
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.loan_schedule_extra_header(),
+                Extra.into(),
+                None,
+            );
+            append_text_column(
+                &schedule_tree_view,
+                &translation_provider.loan_schedule_remaining_header(),
+                Remaining.into(),
+                None,
+            );
+        }
+
+        {
+            let loans = loans.clone();
+            let selected_loan_row = selected_loan_row.clone();
+            let refresh_loans_tree_view = refresh_loans_tree_view.clone();
+            loans_tree_view.connect_key_press_event(move |_, event| {
+                use gdk::enums::key;
+                match event.get_keyval() {
+                    key::Delete => {
+                        if let Some(row) = selected_loan_row.get() {
+                            let mut loans = loans.borrow_mut();
+                            if row < loans.0.len() {
+                                loans.0.remove(row);
+                            }
+                        }
+                        selected_loan_row.set(None);
+                        refresh_loans_tree_view();
+                        Inhibit(true)
+                    }
+                    _ => Inhibit(false),
+                }
+            });
+        }
+
+        // the extra payments go to the selected loan
+        let extra_payment_date_entry = gtk::Entry::new();
+        extra_payment_date_entry.set_text(&today.format(DATE_FORMAT).to_string());
+        let extra_payment_amount_entry = gtk::Entry::new();
+        let extra_payment_button =
+            gtk::Button::new_with_label(&translation_provider.loan_extra_payment_button());
+        {
+            let loans = loans.clone();
+            let selected_loan_row = selected_loan_row.clone();
+            let refresh_loans_tree_view = refresh_loans_tree_view.clone();
+            let extra_payment_date_entry = extra_payment_date_entry.clone();
+            let extra_payment_amount_entry = extra_payment_amount_entry.clone();
+            let decimal_separator = translation_provider.decimal_separator();
+            extra_payment_button.connect_clicked(move |_| {
+                let date = extra_payment_date_entry
+                    .get_text()
+                    .and_then(|text| NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok());
+                let amount = extra_payment_amount_entry
+                    .get_text()
+                    .and_then(|text| MoneyAmount::from_string(text.trim(), &decimal_separator));
+                match (selected_loan_row.get(), date, amount) {
+                    (Some(row), Some(date), Some(amount)) => {
+                        if let Some(loan) = loans.borrow_mut().0.get_mut(row) {
+                            loan.extra_payments.push(ExtraPayment {
+                                date,
+                                amount: amount.to_i32().abs(),
+                            });
+                        }
+                        extra_payment_amount_entry.set_text("");
+                    }
+                    _ => debug!("No loan selected or invalid extra payment, ignoring it"),
+                }
+                refresh_loans_tree_view();
+            });
+        }
+        let extra_payment_box = gtk::Box::new(Horizontal, MARGIN_BETWEEN);
+        extra_payment_box.pack_start(&extra_payment_date_entry, false, false, 0);
+        extra_payment_box.pack_start(&extra_payment_amount_entry, false, false, 0);
+        extra_payment_box.pack_start(&extra_payment_button, false, false, 0);
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&scrolled(&loans_tree_view), true, true, 0);
+        content_area.pack_start(&extra_payment_box, false, false, 0);
+        content_area.pack_start(&scrolled(&schedule_tree_view), true, true, 0);
+        dialog.show_all();
+        dialog.run();
+        dialog.destroy();
+
+        self.model.loans = loans.replace(Default::default());
+        self.model
+            .file_loader
+            .save_loans(&self.model.loans)
+            .unwrap();
+        self.create_due_loan_payments();
+        self.load_selected_period();
+    }
+
+    // every scheduled payment which is due gets its spending, in the file of its month
+    fn create_due_loan_payments(&mut self) {
+        let today = self.model.today;
+        let mut loans_changed = false;
+        for loan in &mut self.model.loans.0 {
+            let due_payments = loan.due_payments(today);
+            let last_payment_date = match due_payments.last() {
+                Some(payment) => payment.date,
+                None => continue,
+            };
+            let (budget_category_id, budget_category_name) =
+                match loan.budget_category_id.and_then(|id| {
                     self.model
+                        .budget_categories
+                        .0
+                        .get(&id)
+                        .map(|name| (id, name))
+                }) {
+                    Some((id, name)) => (id, name.clone()),
+                    // same dummy category as the default spending
+                    None => (
+                        BudgetCategoryId(u32::max_value()),
+                        BudgetCategory(
+                            self.model
+                                .translation_provider
+                                .spending_category_name_placeholder(),
+                        ),
+                    ),
+                };
+
+            for payment in due_payments {
+                let (m, y) = month_year_of(payment.date);
+                let mut monthly_budget = self.model.file_loader.load_monthly_budget(m, y).unwrap();
+                let note = self
+                    .model
+                    .translation_provider
+                    .loan_payment_note(
+                        format_amount(payment.interest, &self.model.translation_provider),
+                        format_amount(payment.principal, &self.model.translation_provider),
+                    )
+                    .expect("Could not format the loan payment note!");
+                let mut amounts = vec![(loan.name.clone(), payment.payment, note)];
+                if payment.extra > 0 {
+                    let extra_name = self
+                        .model
                         .translation_provider
-                        .spending_category_name_placeholder(),
-                );
-                let name = self.model.translation_provider.spending_name_placeholder();
-                let amount = Default::default();
-                let day = self.model.today;
-
-                self.model.monthly_budget.spendings.0.push(Spending {
-                    name,
-                    budget_category_id: *id,
-                    budget_category_name,
-                    amount,
-                    day,
-                });
+                        .loan_extra_payment_name(loan.name.clone())
+                        .expect("Could not format the extra payment name!");
+                    amounts.push((extra_name, payment.extra, String::new()));
+                }
+                for (name, amount, note) in amounts {
+                    monthly_budget.spendings.0.push(Spending {
+                        id: SpendingId::new(),
+                        name,
+                        budget_category_id,
+                        budget_category_name: budget_category_name.clone(),
+                        amount: MoneyAmount::from_i32(-amount),
+                        date: payment.date,
+                        legacy_day: None,
+                        splits: Vec::new(),
+                        tags: Vec::new(),
+                        note,
+                        dismissed_duplicates: Vec::new(),
+                        status: Default::default(),
+                        paid_by: None,
+                        share_rule: Default::default(),
+                        reimbursement: None,
+                        reimbursement_link: None,
+                    });
+                }
+                self.model
+                    .file_loader
+                    .save_monthly_budget(m, y, &monthly_budget)
+                    .unwrap();
+            }
+            debug!(
+                "Created the payments of {} up to {}",
+                loan.name, last_payment_date
+            );
+            loan.paid_through = Some(last_payment_date);
+            loans_changed = true;
+        }
+        if loans_changed {
+            self.model
+                .file_loader
+                .save_loans(&self.model.loans)
+                .unwrap();
+        }
+    }
+
+    fn on_reapply_rules_clicked(&mut self) {
+        let mut changed = 0;
+        for spending in &mut self.model.monthly_budget.spendings.0 {
+            if !spending.is_reconciled()
+                && self
+                    .model
+                    .rules
+                    .apply(spending, &self.model.budget_categories)
+            {
+                changed += 1;
             }
         }
+        debug!("Rules changed the category of {} spendings", changed);
+
+        self.update_budget_categories_gtk_model_from_moneyz_model();
         self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn train_category_classifier(&mut self) {
+        self.model.category_classifier = CategoryClassifier::train(
+            self.model
+                .all_monthly_budgets
+                .iter()
+                .map(|(_, _, monthly_budget)| monthly_budget),
+            &self.model.budget_categories,
+        );
+    }
+
+    fn on_accept_suggestions_clicked(&mut self) {
+        for spending in &mut self.model.monthly_budget.spendings.0 {
+            if spending.budget_category_id != BudgetCategoryId(NO_BUDGET_CATEGORY_ID)
+                || !spending.splits.is_empty()
+                || spending.is_reconciled()
+            {
+                continue;
+            }
+            let suggestion =
+                self.model
+                    .category_classifier
+                    .suggest(spending)
+                    .and_then(|(id, _)| {
+                        self.model
+                            .budget_categories
+                            .0
+                            .get(&id)
+                            .map(|name| (id, name.clone()))
+                    });
+            if let Some((id, name)) = suggestion {
+                spending.budget_category_id = id;
+                spending.budget_category_name = name;
+            }
+        }
+
         self.update_budget_categories_gtk_model_from_moneyz_model();
-        self.model
-            .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
-            .unwrap();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn selected_spending_rows(&self) -> Vec<usize> {
+        let selection = self.spendings_tree_view.get_selection();
+        let (selected_paths, _) = selection.get_selected_rows();
+        let mut selected_spending_rows = selected_paths
+            .iter()
+            .map(|path| self.view_spending_and_split_rows(path))
+            .filter(|(spending_row, split_row)| {
+                split_row.is_none() && *spending_row < self.model.monthly_budget.spendings.0.len()
+            })
+            .map(|(spending_row, _)| spending_row)
+            .collect::<Vec<_>>();
+        selected_spending_rows.sort();
+        selected_spending_rows.dedup();
+        selected_spending_rows
+    }
+
+    fn on_merge_duplicates_clicked(&mut self) {
+        // a spending which is merged into stays, even if it's selected as well
+        let mut merged: Vec<(SpendingId, Option<SpendingId>)> = Vec::new();
+        let mut merged_into: Vec<SpendingId> = Vec::new();
+        for spending_row in self.selected_spending_rows() {
+            let spending_id = self.model.monthly_budget.spendings.0[spending_row].id;
+            if merged_into.contains(&spending_id) || self.is_spending_locked(spending_row) {
+                continue;
+            }
+            match self.model.duplicates.get(spending_row).and_then(|d| *d) {
+                Some(DuplicateOf::SameMonth(other_id)) => {
+                    if !merged.iter().any(|(id, _)| *id == other_id) {
+                        merged.push((spending_id, Some(other_id)));
+                        merged_into.push(other_id);
+                    }
+                }
+                // the other month is not edited, we simply drop this one
+                Some(DuplicateOf::OtherMonth(..)) => merged.push((spending_id, None)),
+                None => (),
+            }
+        }
+
+        let spendings = &mut self.model.monthly_budget.spendings;
+        for (spending_id, other_id) in merged {
+            let removed = match spendings.position(spending_id) {
+                Some(spending_row) => spendings.0.remove(spending_row),
+                None => continue,
+            };
+            if let Some(other_row) = other_id.and_then(|other_id| spendings.position(other_id)) {
+                merge_into(&mut spendings.0[other_row], removed);
+            }
+        }
+
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.update_monthly_total_label_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    // only the pair is dismissed, the spendings can still be found duplicates of other ones
+    fn on_dismiss_duplicates_clicked(&mut self) {
+        for spending_row in self.selected_spending_rows() {
+            let spending_id = self.model.monthly_budget.spendings.0[spending_row].id;
+            let other_id = match self.model.duplicates.get(spending_row).and_then(|d| *d) {
+                Some(DuplicateOf::SameMonth(other_id)) => {
+                    let spendings = &mut self.model.monthly_budget.spendings;
+                    let other = match spendings.position(other_id) {
+                        Some(other_row) => &mut spendings.0[other_row],
+                        None => continue,
+                    };
+                    if !other.dismissed_duplicates.contains(&spending_id) {
+                        other.dismissed_duplicates.push(spending_id);
+                    }
+                    other_id
+                }
+                // the other month is not edited, the pair is checked both ways anyway
+                Some(DuplicateOf::OtherMonth(other_id)) => other_id,
+                None => continue,
+            };
+            let spending = &mut self.model.monthly_budget.spendings.0[spending_row];
+            if !spending.dismissed_duplicates.contains(&other_id) {
+                spending.dismissed_duplicates.push(other_id);
+            }
+        }
+
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
     }
 
     fn on_budget_categories_delete_key_pressed(&mut self) {
@@ -592,19 +4102,7 @@ impl Widget for MainWindow {
         let (selected_paths, _) = selection.get_selected_rows();
         let selected_budget_category_ids = selected_paths
             .iter()
-            .filter_map(|path| {
-                let budget_category_row = path.get_indices()[0] as usize;
-                match self
-                    .model
-                    .budget_categories
-                    .0
-                    .iter()
-                    .nth(budget_category_row)
-                {
-                    Some((id, _)) => Some(*id),
-                    None => None,
-                }
-            })
+            .filter_map(|path| self.budget_category_id_from_path(path))
             .collect::<Vec<_>>();
 
         for selected_budget_category_id in selected_budget_category_ids {
@@ -612,22 +4110,23 @@ impl Widget for MainWindow {
                 .budget_categories
                 .0
                 .remove(&selected_budget_category_id);
+            self.model
+                .budget_category_parents
+                .remove(selected_budget_category_id);
         }
 
         self.update_budget_categories_gtk_model_from_moneyz_model();
         self.update_monthly_budget_gtk_model_from_moneyz_model();
-        let category_model: gtk::ListStore = (&self.model.budget_categories).into();
-        let tree_model = category_model.upcast::<gtk::TreeModel>();
-        self.model
-            .spending_category_combox_box
-            .as_ref()
-            .unwrap()
-            .set_property_model(Some(&tree_model));
+        self.update_budget_category_combo_boxes_models();
 
         self.model
             .file_loader
             .save_budget_categories(&self.model.budget_categories)
             .unwrap();
+        self.model
+            .file_loader
+            .save_budget_category_parents(&self.model.budget_category_parents)
+            .unwrap();
     }
 
     fn on_spendings_delete_key_pressed(&mut self) {
@@ -635,116 +4134,741 @@ impl Widget for MainWindow {
         let (selected_paths, _) = selection.get_selected_rows();
         let mut selected_spending_ids = selected_paths
             .iter()
-            .filter_map(|path| {
-                let spending_category_row = path.get_indices()[0] as usize;
+            .map(|path| self.view_spending_and_split_rows(path))
+            .filter(|(spending_category_row, _)| !self.is_spending_locked(*spending_category_row))
+            .filter(|(spending_category_row, split_row)| {
                 match self
                     .model
                     .monthly_budget
                     .spendings
                     .0
-                    .get(spending_category_row)
+                    .get(*spending_category_row)
+                {
+                    Some(spending) => {
+                        split_row.map_or(true, |split_row| split_row < spending.splits.len())
+                    }
+                    None => false,
+                }
+            })
+            .collect::<Vec<_>>();
+        // highest indices first, and the split lines before their spending, so that removing an
+        // entry never shifts the ones we still have to remove
+        selected_spending_ids.sort_by(|a, b| b.cmp(a));
+
+        for (selected_spending_id, selected_split_id) in selected_spending_ids {
+            let spendings = &mut self.model.monthly_budget.spendings.0;
+            match selected_split_id {
+                Some(selected_split_id) => {
+                    spendings[selected_spending_id]
+                        .splits
+                        .remove(selected_split_id);
+                }
+                None => {
+                    spendings.remove(selected_spending_id);
+                }
+            }
+        }
+
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn on_split_spending_clicked(&mut self) {
+        let selection = self.spendings_tree_view.get_selection();
+        let (selected_paths, _) = selection.get_selected_rows();
+        for path in selected_paths {
+            let (spending_category_row, _) = self.view_spending_and_split_rows(&path);
+            if self.is_spending_locked(spending_category_row) {
+                continue;
+            }
+            let spending = if let Some(spending) = self
+                .model
+                .monthly_budget
+                .spendings
+                .0
+                .get_mut(spending_category_row)
+            {
+                spending
+            } else {
+                continue;
+            };
+
+            // the new line takes whatever is not split yet, to be adjusted by the user
+            debug!("Adding a split line to the spending '{}'", spending.name);
+            let amount = MoneyAmount::from_i32(spending.unsplit_amount());
+            spending.splits.push(SpendingSplit {
+                budget_category_id: spending.budget_category_id,
+                budget_category_name: spending.budget_category_name.clone(),
+                amount,
+                memo: self.model.translation_provider.split_memo_placeholder(),
+            });
+        }
+
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.save_selected_period();
+    }
+
+    fn load_selected_period(&mut self) {
+        debug!(
+            "Selected period: {} to {}",
+            self.model.selected_period.start, self.model.selected_period.end
+        );
+
+        // the months edited since the last training are taken into account
+        self.load_all_monthly_budgets();
+        self.train_category_classifier();
+        self.model.monthly_budget = self
+            .model
+            .file_loader
+            .load_period_budget(&self.model.selected_period)
+            .unwrap();
+        self.load_adjacent_monthly_budgets();
+        self.update_period_label();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.update_monthly_total_label_from_moneyz_model();
+        self.update_goals_box();
+        // the alerts already true when the period is opened aren't notified
+        self.check_alerts(false);
+
+        // to add new entries, we add a "default" line to the gtk model
+        // it does NOT exist in the actual model
+    }
+
+    // a month which can't be read is left out, instead of taking the whole window down
+    fn load_all_monthly_budgets(&mut self) {
+        self.model.all_monthly_budgets = match self.model.file_loader.load_all_monthly_budgets() {
+            Ok(monthly_budgets) => monthly_budgets,
+            Err(e) => {
+                debug!("Could not load every month: {}", e);
+                Vec::new()
+            }
+        };
+    }
+
+    // the months written since the period was opened
+    fn reload_months(&mut self, months: &[(Month, Year)]) {
+        for (m, y) in months {
+            let monthly_budget = match self.model.file_loader.load_monthly_budget(*m, *y) {
+                Ok(monthly_budget) => monthly_budget,
+                Err(e) => {
+                    debug!("Could not reload {}-{:02}: {}", y.0, m.id(), e);
+                    continue;
+                }
+            };
+            let all_monthly_budgets = &mut self.model.all_monthly_budgets;
+            match all_monthly_budgets
+                .iter()
+                .position(|(other_m, other_y, _)| other_m == m && other_y == y)
+            {
+                Some(idx) => all_monthly_budgets[idx].2 = monthly_budget,
+                // kept in chronological order
+                None => {
+                    let idx = all_monthly_budgets
+                        .iter()
+                        .position(|(other_m, other_y, _)| (other_y.0, other_m.id()) > (y.0, m.id()))
+                        .unwrap_or_else(|| all_monthly_budgets.len());
+                    all_monthly_budgets.insert(idx, (*m, *y, monthly_budget));
+                }
+            }
+        }
+    }
+
+    fn load_adjacent_monthly_budgets(&mut self) {
+        let period = self.model.selected_period;
+        let months = period.months();
+        let (first_m, first_y) = months[0];
+        let (last_m, last_y) = months[months.len() - 1];
+        let mut adjacent_months = vec![previous_month(first_m, first_y)];
+        adjacent_months.extend(months);
+        adjacent_months.push(next_month(last_m, last_y));
+
+        let file_loader = &self.model.file_loader;
+        self.model.adjacent_monthly_budgets = adjacent_months
+            .into_iter()
+            .map(|(m, y)| {
+                let mut monthly_budget = file_loader.load_monthly_budget(m, y).unwrap();
+                // the spendings of the period are the ones being edited
+                monthly_budget
+                    .spendings
+                    .0
+                    .retain(|spending| !period.contains(spending.date));
+                (m, y, monthly_budget)
+            })
+            .collect();
+    }
+
+    fn update_period_label(&self) {
+        let period = &self.model.selected_period;
+        let text = match period.as_calendar_month() {
+            Some((m, y)) => format!("{} {}", self.month_to_name(m), y.0),
+            None => self
+                .model
+                .translation_provider
+                .budget_period_range(
+                    period.start.format(DATE_FORMAT).to_string(),
+                    period.end.format(DATE_FORMAT).to_string(),
+                )
+                .expect("Could not format the budget period!"),
+        };
+        self.period_label.set_text(&text);
+    }
+
+    fn on_previous_period_clicked(&mut self) {
+        self.model.selected_period = self
+            .model
+            .config
+            .budget_period
+            .previous(&self.model.selected_period);
+        self.load_selected_period();
+    }
+
+    fn on_next_period_clicked(&mut self) {
+        self.model.selected_period = self
+            .model
+            .config
+            .budget_period
+            .next(&self.model.selected_period);
+        self.load_selected_period();
+    }
+
+    fn on_budget_period_clicked(&mut self) {
+        let translation_provider = &self.model.translation_provider;
+        let ok_label = translation_provider.ok_button();
+        let cancel_label = translation_provider.cancel_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.budget_period_button()),
+            Some(&self.root()),
+            flags,
+            &[
+                (ok_label.as_str(), gtk::ResponseType::Ok),
+                (cancel_label.as_str(), gtk::ResponseType::Cancel),
+            ],
+        );
+
+        let kind_combo_box = gtk::ComboBoxText::new();
+        kind_combo_box.append(
+            Some(PERIOD_CALENDAR_MONTH_ID),
+            &translation_provider.budget_period_calendar_month(),
+        );
+        kind_combo_box.append(
+            Some(PERIOD_MONTH_STARTING_ON_ID),
+            &translation_provider.budget_period_month_starting_on(),
+        );
+        kind_combo_box.append(
+            Some(PERIOD_WEEKLY_ID),
+            &translation_provider.budget_period_weekly(),
+        );
+        kind_combo_box.append(
+            Some(PERIOD_BI_WEEKLY_ID),
+            &translation_provider.budget_period_bi_weekly(),
+        );
+        let start_day_spin_button = gtk::SpinButton::new_with_range(1.0, 31.0, 1.0);
+        let anchor_entry = gtk::Entry::new();
+        anchor_entry.set_text(
+            &self
+                .model
+                .selected_period
+                .start
+                .format(DATE_FORMAT)
+                .to_string(),
+        );
+        match self.model.config.budget_period {
+            BudgetPeriodKind::CalendarMonth => {
+                kind_combo_box.set_active_id(Some(PERIOD_CALENDAR_MONTH_ID));
+            }
+            BudgetPeriodKind::MonthStartingOn { day } => {
+                kind_combo_box.set_active_id(Some(PERIOD_MONTH_STARTING_ON_ID));
+                start_day_spin_button.set_value(f64::from(day));
+            }
+            BudgetPeriodKind::Weekly { anchor } => {
+                kind_combo_box.set_active_id(Some(PERIOD_WEEKLY_ID));
+                anchor_entry.set_text(&anchor.format(DATE_FORMAT).to_string());
+            }
+            BudgetPeriodKind::BiWeekly { anchor } => {
+                kind_combo_box.set_active_id(Some(PERIOD_BI_WEEKLY_ID));
+                anchor_entry.set_text(&anchor.format(DATE_FORMAT).to_string());
+            }
+        }
+
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(MARGIN_BETWEEN as u32);
+        grid.set_column_spacing(MARGIN_BETWEEN as u32);
+        grid.attach(&kind_combo_box, 0, 0, 2, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.budget_period_start_day())),
+            0,
+            1,
+            1,
+            1,
+        );
+        grid.attach(&start_day_spin_button, 1, 1, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.budget_period_anchor())),
+            0,
+            2,
+            1,
+            1,
+        );
+        grid.attach(&anchor_entry, 1, 2, 1, 1);
+
+        dialog.get_content_area().pack_start(&grid, true, true, 0);
+        dialog.show_all();
+        let response = dialog.run();
+
+        let anchor = anchor_entry
+            .get_text()
+            .and_then(|text| NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok());
+        let budget_period = match (
+            kind_combo_box
+                .get_active_id()
+                .as_ref()
+                .map(|id| id.as_str()),
+            anchor,
+        ) {
+            (Some(PERIOD_CALENDAR_MONTH_ID), _) => Some(BudgetPeriodKind::CalendarMonth),
+            (Some(PERIOD_MONTH_STARTING_ON_ID), _) => Some(BudgetPeriodKind::MonthStartingOn {
+                day: start_day_spin_button.get_value_as_int() as u32,
+            }),
+            (Some(PERIOD_WEEKLY_ID), Some(anchor)) => Some(BudgetPeriodKind::Weekly { anchor }),
+            (Some(PERIOD_BI_WEEKLY_ID), Some(anchor)) => {
+                Some(BudgetPeriodKind::BiWeekly { anchor })
+            }
+            _ => None,
+        };
+        dialog.destroy();
+
+        if response != gtk::ResponseType::Ok {
+            return;
+        }
+        let budget_period = if let Some(budget_period) = budget_period {
+            budget_period
+        } else {
+            debug!("Invalid budget period, ignoring it");
+            return;
+        };
+
+        self.model.config.budget_period = budget_period;
+        self.model
+            .file_loader
+            .save_config(&self.model.config)
+            .expect("Could not save configuration file!");
+        // we stay around the same dates
+        self.model.selected_period =
+            budget_period.period_containing(self.model.selected_period.start);
+        self.load_selected_period();
+    }
+
+    fn on_language_changed(&mut self) {
+        let new_language = if let Some(new_language) = self.language_combo_box.get_active_id() {
+            new_language.to_string()
+        } else {
+            return;
+        };
+        // since the callback is called on startup (when we initialize the selected language),
+        // we need this check
+        if new_language == self.model.config.language {
+            return;
+        }
+        self.model.config.language = new_language;
+        self.model
+            .file_loader
+            .save_config(&self.model.config)
+            .expect("Could not save configuration file!");
+
+        // the dialogs get their provider when opened, only the main window has to be redone
+        self.model.translation_provider =
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!");
+        self.translate_widgets();
+        self.update_budget_category_combo_boxes_models();
+        self.update_member_combo_box_model();
+        self.update_period_label();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
+        self.update_monthly_total_label_from_moneyz_model();
+        self.update_goals_box();
+        self.check_alerts(false);
+    }
+
+    fn on_budgeting_mode_toggled(&mut self) {
+        let budgeting_mode = if self.zero_based_check_button.get_active() {
+            BudgetingMode::ZeroBased
+        } else {
+            BudgetingMode::Classic
+        };
+        // same as the language: the callback is called on startup
+        if budgeting_mode == self.model.config.budgeting_mode {
+            return;
+        }
+        debug!("Budgeting mode changed to {:?}", budgeting_mode);
+        self.model.config.budgeting_mode = budgeting_mode;
+        self.model
+            .file_loader
+            .save_config(&self.model.config)
+            .expect("Could not save configuration file!");
+
+        self.update_budgeting_mode_widgets_visibility();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
+        self.update_monthly_total_label_from_moneyz_model();
+    }
+
+    fn on_reconcile_clicked(&mut self) {
+        // every month is loaded, as the statement may cover spendings from any of them
+        let monthly_budgets = Rc::new(RefCell::new(
+            self.model.file_loader.load_all_monthly_budgets().unwrap(),
+        ));
+        let changed_months = Rc::new(RefCell::new(Vec::new()));
+        let translation_provider = Rc::new(
+            TranslationProvider::get_provider(&self.model.config.language)
+                .expect("Language ID does not exist!"),
+        );
+
+        let finish_label = translation_provider.reconcile_finish_button();
+        let cancel_label = translation_provider.cancel_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.reconcile_button()),
+            Some(&self.root()),
+            flags,
+            &[
+                (finish_label.as_str(), gtk::ResponseType::Ok),
+                (cancel_label.as_str(), gtk::ResponseType::Cancel),
+            ],
+        );
+
+        let calendar = gtk::Calendar::new();
+        let default_end = self.default_spending_date();
+        calendar.select_month(default_end.month0(), default_end.year() as u32);
+        calendar.select_day(default_end.day());
+        let closing_balance_entry = gtk::Entry::new();
+        let cleared_balance_label = gtk::Label::new(None);
+        let difference_label = gtk::Label::new(None);
+
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(MARGIN_BETWEEN as u32);
+        grid.set_column_spacing(MARGIN_BETWEEN as u32);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.reconcile_end_date())),
+            0,
+            0,
+            1,
+            1,
+        );
+        grid.attach(&calendar, 1, 0, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.reconcile_closing_balance())),
+            0,
+            1,
+            1,
+            1,
+        );
+        grid.attach(&closing_balance_entry, 1, 1, 1, 1);
+        grid.attach(&cleared_balance_label, 0, 2, 2, 1);
+        grid.attach(&difference_label, 0, 3, 2, 1);
+
+        let refresh_labels: Rc<dyn Fn()> = {
+            let monthly_budgets = monthly_budgets.clone();
+            let calendar = calendar.clone();
+            let closing_balance_entry = closing_balance_entry.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                let cleared_balance =
+                    cleared_balance(&monthly_budgets.borrow(), calendar_date(&calendar));
+                cleared_balance_label.set_text(
+                    &translation_provider
+                        .reconcile_cleared_balance(format_amount(
+                            cleared_balance,
+                            &translation_provider,
+                        ))
+                        .expect("Could not format the cleared balance!"),
+                );
+                // the difference goes down to zero as the spendings are ticked off
+                let difference = closing_balance_entry
+                    .get_text()
+                    .and_then(|text| {
+                        MoneyAmount::from_string(&text, &translation_provider.decimal_separator())
+                    })
+                    .map_or_else(String::new, |closing_balance| {
+                        translation_provider
+                            .reconcile_difference(format_amount(
+                                closing_balance.to_i32() - cleared_balance,
+                                &translation_provider,
+                            ))
+                            .expect("Could not format the difference!")
+                    });
+                difference_label.set_text(&difference);
+            })
+        };
+        {
+            let refresh_labels = refresh_labels.clone();
+            closing_balance_entry.connect_changed(move |_| refresh_labels());
+        }
+
+        let spendings_tree_view = gtk::TreeView::new();
+        {
+            use ReconcileListStoreIds::*;
+            let col = gtk::TreeViewColumn::new();
+            col.set_title(&translation_provider.reconcile_cleared_header());
+            let cell = gtk::CellRendererToggle::new();
+            col.pack_start(&cell, true);
+            col.add_attribute(&cell, "active", Cleared.into());
+            spendings_tree_view.append_column(&col);
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_day_header(),
+                Date.into(),
+                None,
+            );
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_name_header(),
+                Name.into(),
+                None,
+            );
+            append_text_column(
+                &spendings_tree_view,
+                &translation_provider.spending_amount_header(),
+                Amount.into(),
+                Some(AmountBackgroundColor.into()),
+            );
+
+            let monthly_budgets = monthly_budgets.clone();
+            let changed_months = changed_months.clone();
+            let spendings_tree_view = spendings_tree_view.clone();
+            let refresh_labels = refresh_labels.clone();
+            cell.connect_toggled(move |cell, path| {
+                let model = spendings_tree_view
+                    .get_model()
+                    .unwrap()
+                    .downcast::<gtk::ListStore>()
+                    .unwrap();
+                let iter = model.get_iter(&path).unwrap();
+                let month_idx = model
+                    .get_value(&iter, MonthIndex.into())
+                    .get::<u32>()
+                    .unwrap() as usize;
+                let spending_idx = model
+                    .get_value(&iter, SpendingIndex.into())
+                    .get::<u32>()
+                    .unwrap() as usize;
+                let cleared = !cell.get_active();
+                monthly_budgets.borrow_mut()[month_idx].2.spendings.0[spending_idx].status =
+                    if cleared {
+                        ClearedStatus::Cleared
+                    } else {
+                        ClearedStatus::Uncleared
+                    };
+                model.set_value(&iter, Cleared.into(), &cleared.to_value());
+                if !changed_months.borrow().contains(&month_idx) {
+                    changed_months.borrow_mut().push(month_idx);
+                }
+                refresh_labels();
+            });
+        }
+
+        let refresh_spendings: Rc<dyn Fn()> = {
+            let monthly_budgets = monthly_budgets.clone();
+            let calendar = calendar.clone();
+            let spendings_tree_view = spendings_tree_view.clone();
+            let translation_provider = translation_provider.clone();
+            Rc::new(move || {
+                spendings_tree_view.set_model(Some(&get_reconcile_model(
+                    &monthly_budgets.borrow(),
+                    calendar_date(&calendar),
+                    &translation_provider,
+                )));
+                refresh_labels();
+            })
+        };
+        {
+            let refresh_spendings = refresh_spendings.clone();
+            calendar.connect_day_selected(move |_| refresh_spendings());
+        }
+        refresh_spendings();
+
+        let content_area = dialog.get_content_area();
+        content_area.pack_start(&grid, false, false, 0);
+        content_area.pack_start(&scrolled(&spendings_tree_view), true, true, 0);
+        dialog.show_all();
+        let response = dialog.run();
+
+        let end = calendar_date(&calendar);
+        let closing_balance = closing_balance_entry.get_text().and_then(|text| {
+            MoneyAmount::from_string(&text, &translation_provider.decimal_separator())
+        });
+        dialog.destroy();
+
+        let mut monthly_budgets = monthly_budgets.borrow_mut();
+        let mut changed_months = changed_months.borrow_mut();
+        if response == gtk::ResponseType::Ok {
+            // the cleared spendings have to match the statement before being locked
+            match closing_balance {
+                Some(closing_balance)
+                    if closing_balance.to_i32() == cleared_balance(&monthly_budgets, end) =>
                 {
-                    Some(_) => Some(spending_category_row),
-                    None => None,
+                    for month_idx in reconcile(&mut monthly_budgets, end) {
+                        if !changed_months.contains(&month_idx) {
+                            changed_months.push(month_idx);
+                        }
+                    }
                 }
-            })
-            .collect::<Vec<_>>();
-        selected_spending_ids.sort_by(|a, b| b.cmp(a));
+                _ => debug!("The cleared balance does not match the statement, not reconciling"),
+            }
+        }
 
-        for selected_spending_id in selected_spending_ids {
+        // the ticked off spendings are kept even when the reconciliation isn't finished
+        let mut months = Vec::new();
+        for month_idx in changed_months.iter() {
+            let (m, y, monthly_budget) = &monthly_budgets[*month_idx];
             self.model
-                .monthly_budget
-                .spendings
-                .0
-                .remove(selected_spending_id);
+                .file_loader
+                .save_monthly_budget(*m, *y, monthly_budget)
+                .unwrap();
+            months.push((*m, *y));
         }
-
-        self.update_budget_categories_gtk_model_from_moneyz_model();
-        self.update_monthly_budget_gtk_model_from_moneyz_model();
-        self.model
+        self.reload_months(&months);
+        self.model.monthly_budget = self
+            .model
             .file_loader
-            .save_monthly_budget(
-                self.model.selected_month,
-                self.model.selected_year,
-                &self.model.monthly_budget,
-            )
+            .load_period_budget(&self.model.selected_period)
             .unwrap();
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
     }
 
-    fn on_change_selected_date(&mut self) {
-        let selected_month_id = if let Some(id) = self.month_combo_box.get_active() {
-            id
-        } else {
-            return;
-        };
-        self.model.selected_month = num_traits::FromPrimitive::from_u32(selected_month_id).unwrap();
-        debug!(
-            "month_combo_box: id is {}, which corresponds to the month {}",
-            selected_month_id,
-            self.month_to_name(self.model.selected_month),
+    fn on_move_money_clicked(&mut self) {
+        let translation_provider = &self.model.translation_provider;
+        let budget_categories = &self.model.budget_categories;
+        let ok_label = translation_provider.ok_button();
+        let cancel_label = translation_provider.cancel_button();
+        let mut flags = gtk::DialogFlags::MODAL;
+        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
+        let dialog = gtk::Dialog::new_with_buttons(
+            Some(&translation_provider.move_money_button()),
+            Some(&self.root()),
+            flags,
+            &[
+                (ok_label.as_str(), gtk::ResponseType::Ok),
+                (cancel_label.as_str(), gtk::ResponseType::Cancel),
+            ],
         );
 
-        self.model.selected_year = if let Some(id) = self.year_combo_box.get_active() {
-            Year(id + FIRST_YEAR)
-        } else {
-            return;
-        };
-        debug!("year_combo_box: year is {}", self.model.selected_year.0);
+        let from_combo_box = gtk::ComboBoxText::new();
+        let to_combo_box = gtk::ComboBoxText::new();
+        for (id, budget_category) in &budget_categories.0 {
+            from_combo_box.append(Some(&id.0.to_string()), &budget_category.0);
+            to_combo_box.append(Some(&id.0.to_string()), &budget_category.0);
+        }
+        let amount_entry = gtk::Entry::new();
 
-        self.model.monthly_budget = self
+        let grid = gtk::Grid::new();
+        grid.set_row_spacing(MARGIN_BETWEEN as u32);
+        grid.set_column_spacing(MARGIN_BETWEEN as u32);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.move_money_from())),
+            0,
+            0,
+            1,
+            1,
+        );
+        grid.attach(&from_combo_box, 1, 0, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.move_money_to())),
+            0,
+            1,
+            1,
+            1,
+        );
+        grid.attach(&to_combo_box, 1, 1, 1, 1);
+        grid.attach(
+            &gtk::Label::new(Some(&translation_provider.move_money_amount())),
+            0,
+            2,
+            1,
+            1,
+        );
+        grid.attach(&amount_entry, 1, 2, 1, 1);
+
+        // the log of this month's transfers
+        let category_name = |id: &BudgetCategoryId| {
+            budget_categories
+                .0
+                .get(id)
+                .map_or_else(String::new, |budget_category| budget_category.0.clone())
+        };
+        let history = self
             .model
-            .file_loader
-            .load_monthly_budget(self.model.selected_month, self.model.selected_year)
-            .unwrap();
-        self.update_budget_categories_gtk_model_from_moneyz_model();
-        self.update_monthly_budget_gtk_model_from_moneyz_model();
-        self.update_monthly_total_label_from_moneyz_model();
-        let day_model =
-            list_model_from_month_year(self.model.selected_month, self.model.selected_year);
-        let tree_model = day_model.upcast::<gtk::TreeModel>();
-        self.model
-            .spending_day_combox_box
-            .as_ref()
-            .unwrap()
-            .set_property_model(Some(&tree_model));
+            .monthly_budget
+            .envelope_transfers
+            .iter()
+            .map(|transfer| {
+                translation_provider
+                    .envelope_transfer_entry(
+                        transfer.day.0.to_string(),
+                        category_name(&transfer.from),
+                        category_name(&transfer.to),
+                        format_amount(transfer.amount.0, translation_provider),
+                    )
+                    .expect("Could not format the envelope transfer!")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        grid.attach(&gtk::Label::new(Some(&history)), 0, 3, 2, 1);
 
-        // to add new entries, we add a "default" line to the gtk model
-        // it does NOT exist in the actual model
-    }
+        dialog.get_content_area().pack_start(&grid, true, true, 0);
+        dialog.show_all();
+        let response = dialog.run();
 
-    fn on_language_changed(&mut self) {
-        let new_language = if let Some(new_language) = self.language_combo_box.get_active_id() {
-            new_language.to_string()
-        } else {
-            return;
-        };
-        // since the callback is called on startup (when we initialize the selected language),
-        // we need this check
-        if new_language == self.model.config.language {
+        let from = from_combo_box
+            .get_active_id()
+            .and_then(|id| id.parse::<u32>().ok())
+            .map(BudgetCategoryId);
+        let to = to_combo_box
+            .get_active_id()
+            .and_then(|id| id.parse::<u32>().ok())
+            .map(BudgetCategoryId);
+        let amount = amount_entry.get_text().and_then(|text| {
+            MoneyAmount::from_string(&text, &translation_provider.decimal_separator())
+        });
+        dialog.destroy();
+
+        if response != gtk::ResponseType::Ok {
             return;
         }
-        self.model.config.language = new_language;
+        let (from, to, amount) = match (from, to, amount) {
+            (Some(from), Some(to), Some(amount)) if from != to && amount.to_i32() > 0 => {
+                (from, to, amount)
+            }
+            _ => {
+                debug!("Invalid envelope transfer, ignoring it");
+                return;
+            }
+        };
+        debug!(
+            "Moving {} from envelope {} to envelope {}",
+            amount.to_i32(),
+            from.0,
+            to.0
+        );
+
         self.model
-            .file_loader
-            .save_config(&self.model.config)
-            .expect("Could not save configuration file!");
+            .monthly_budget
+            .envelope_transfers
+            .push(EnvelopeTransfer {
+                from,
+                to,
+                amount: BudgetAmount(amount.to_i32()),
+                // same as a new spending: today only when it's in the selected period
+                day: Day(self.default_spending_date().day() as i32),
+            });
+        self.save_selected_period();
 
-        let new_language_provider = TranslationProvider::get_provider(&self.model.config.language)
-            .expect("Language ID does not exist!");
-        let message_dialog_text = new_language_provider.restart_required_info()
-            + "\n\n"
-            + &self.model.translation_provider.restart_required_info();
-        let mut flags = gtk::DialogFlags::MODAL;
-        flags.insert(gtk::DialogFlags::DESTROY_WITH_PARENT);
-        let message_dialog = gtk::MessageDialog::new(
-            Some(&self.root()),
-            flags,
-            gtk::MessageType::Info,
-            gtk::ButtonsType::Close,
-            &message_dialog_text,
-        );
-        message_dialog.run();
-        message_dialog.emit_close();
+        self.update_budget_categories_gtk_model_from_moneyz_model();
     }
 
     fn update(&mut self, event: MoneyzMsg) {
@@ -753,18 +4877,54 @@ impl Widget for MainWindow {
         match event {
             BudgetAmountChanged(path, value) => self.on_budget_amount_changed(path, value),
             CategoryNameChanged(path, value) => self.on_category_name_changed(path, value),
+            CategoryParentChanged(path, value) => self.on_category_parent_changed(path, value),
             SpendingAmountCellChanged(path, value) => {
                 self.on_spending_amount_cell_changed(path, value)
             }
             SpendingNameCellChanged(path, value) => self.on_spending_name_cell_changed(path, value),
-            SpendingDayCellChanged(path, value) => self.on_spending_day_cell_changed(path, value),
+            SpendingDateCellChanged(path, value) => self.on_spending_date_cell_changed(path, value),
             SpendingCategoryCellChanged(path, value) => {
                 self.on_spending_category_cell_changed(path, value)
             }
             BudgetCategoriesDeleteKeyPressed => self.on_budget_categories_delete_key_pressed(),
             SpendingsDeleteKeyPressed => self.on_spendings_delete_key_pressed(),
-            ChangeSelectedDate => self.on_change_selected_date(),
+            PreviousPeriodClicked => self.on_previous_period_clicked(),
+            NextPeriodClicked => self.on_next_period_clicked(),
+            BudgetPeriodClicked => self.on_budget_period_clicked(),
             LanguageChanged => self.on_language_changed(),
+            BudgetingModeToggled => self.on_budgeting_mode_toggled(),
+            MoveMoneyClicked => self.on_move_money_clicked(),
+            SplitSpendingClicked => self.on_split_spending_clicked(),
+            SpendingTagsCellChanged(path, value) => self.on_spending_tags_cell_changed(path, value),
+            SpendingNoteCellChanged(path, value) => self.on_spending_note_cell_changed(path, value),
+            SpendingPaidByCellChanged(path, value) => {
+                self.on_spending_paid_by_cell_changed(path, value)
+            }
+            SpendingShareCellChanged(path, value) => {
+                self.on_spending_share_cell_changed(path, value)
+            }
+            SpendingReimbursementCellChanged(path, value) => {
+                self.on_spending_reimbursement_cell_changed(path, value)
+            }
+            TagsReportClicked => self.on_tags_report_clicked(),
+            PayeesClicked => self.on_payees_clicked(),
+            RulesClicked => self.on_rules_clicked(),
+            ReapplyRulesClicked => self.on_reapply_rules_clicked(),
+            AcceptSuggestionsClicked => self.on_accept_suggestions_clicked(),
+            MergeDuplicatesClicked => self.on_merge_duplicates_clicked(),
+            DismissDuplicatesClicked => self.on_dismiss_duplicates_clicked(),
+            ReconcileClicked => self.on_reconcile_clicked(),
+            GoalsClicked => self.on_goals_clicked(),
+            ForecastClicked => self.on_forecast_clicked(),
+            NetWorthClicked => self.on_net_worth_clicked(),
+            LoansClicked => self.on_loans_clicked(),
+            MembersClicked => self.on_members_clicked(),
+            SettleUpClicked => self.on_settle_up_clicked(),
+            ReimbursementsClicked => self.on_reimbursements_clicked(),
+            AlertsClicked => self.on_alerts_clicked(),
+            BillsClicked => self.on_bills_clicked(),
+            SearchActivated => self.on_search_activated(),
+            SpendingsFilterChanged => self.on_spendings_filter_changed(),
             Quit => gtk::main_quit(),
         }
     }
@@ -778,15 +4938,31 @@ impl Widget for MainWindow {
                     orientation: Vertical,
                     gtk::Box {
                         orientation: Horizontal,
-                        #[name="month_combo_box"]
-                        gtk::ComboBox {
-                            changed(_) => MoneyzMsg::ChangeSelectedDate,
+                        #[name="previous_period_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::PreviousPeriodClicked,
+                            label: "<",
                             margin_start: MARGIN_LEFT,
                             margin_end: MARGIN_BETWEEN,
                         },
-                        #[name="year_combo_box"]
-                        gtk::ComboBox {
-                            changed(_) => MoneyzMsg::ChangeSelectedDate,
+                        #[name="period_label"]
+                        gtk::Label {
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="next_period_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::NextPeriodClicked,
+                            label: ">",
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="budget_period_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::BudgetPeriodClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="search_entry"]
+                        gtk::SearchEntry {
+                            activate => MoneyzMsg::SearchActivated,
                             margin_end: MARGIN_BETWEEN,
                         },
                         #[name="language_combo_box"]
@@ -794,6 +4970,107 @@ impl Widget for MainWindow {
                             changed(_) => MoneyzMsg::LanguageChanged,
                             margin_end: MARGIN_BETWEEN,
                         },
+                        #[name="split_spending_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::SplitSpendingClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="tags_report_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::TagsReportClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="payees_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::PayeesClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="rules_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::RulesClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="reapply_rules_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::ReapplyRulesClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="accept_suggestions_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::AcceptSuggestionsClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="merge_duplicates_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::MergeDuplicatesClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="dismiss_duplicates_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::DismissDuplicatesClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="reconcile_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::ReconcileClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="goals_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::GoalsClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="forecast_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::ForecastClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="net_worth_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::NetWorthClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="loans_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::LoansClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="members_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::MembersClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="settle_up_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::SettleUpClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="reimbursements_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::ReimbursementsClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="alerts_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::AlertsClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="bills_button"]
+                        gtk::Button {
+                            clicked => MoneyzMsg::BillsClicked,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                        #[name="zero_based_check_button"]
+                        gtk::CheckButton {
+                            toggled => MoneyzMsg::BudgetingModeToggled,
+                            margin_end: MARGIN_BETWEEN,
+                        },
+                    },
+                    #[name="spendings_filter_entry"]
+                    gtk::SearchEntry {
+                        search_changed => MoneyzMsg::SpendingsFilterChanged,
+                        margin_start: MARGIN_LEFT,
+                        margin_end: MARGIN_LEFT,
                     },
                     #[name="spendings_tree_view"]
                     gtk::TreeView {
@@ -808,10 +5085,23 @@ impl Widget for MainWindow {
                     gtk::Label {
                         text: ""
                     },
+                    #[name="ready_to_assign_label"]
+                    gtk::Label {
+                        text: ""
+                    },
+                    #[name="move_money_button"]
+                    gtk::Button {
+                        clicked => MoneyzMsg::MoveMoneyClicked,
+                    },
                     #[name="budget_categories_tree_view"]
                     gtk::TreeView {
                         margin_start: MARGIN_LEFT,
                     },
+                    #[name="goals_box"]
+                    gtk::Box {
+                        margin_start: MARGIN_LEFT,
+                        orientation: Vertical,
+                    },
                 },
             },
             delete_event(_, _) => (MoneyzMsg::Quit, Inhibit(false)),
@@ -821,36 +5111,201 @@ impl Widget for MainWindow {
     fn init_view(&mut self) {
         self.initialize_budget_categories_headers();
         self.initialize_spendings_tree_view_headers();
-        self.initialize_month_year_combo_boxes();
         self.initialize_language_combo_box();
+        self.initialize_budgeting_mode_widgets();
+        self.translate_widgets();
 
-        // everything else is gonna be loaded bby the "on_change_selected_date" event
         self.model.budget_categories = self.model.file_loader.load_budget_categories().unwrap();
+        self.model.budget_category_parents = self
+            .model
+            .file_loader
+            .load_budget_category_parents()
+            .unwrap();
+        self.model.payees = self.model.file_loader.load_payees().unwrap();
+        self.model.members = self.model.file_loader.load_members().unwrap();
+        self.update_member_combo_box_model();
+        self.model.rules = self.model.file_loader.load_rules().unwrap();
+        self.model.goals = self.model.file_loader.load_goals().unwrap();
+        self.model.loans = self.model.file_loader.load_loans().unwrap();
+        self.model.bills = self.model.file_loader.load_bills().unwrap();
+        self.create_due_loan_payments();
+        fill_payee_completion_model(&self.model.payee_completion_model, &self.model.payees);
+        self.load_selected_period();
+    }
+
+    // everything showing a translation which isn't rebuilt with the period
+    fn translate_widgets(&self) {
+        self.translate_tree_views_headers();
+        self.zero_based_check_button
+            .set_label(&self.model.translation_provider.zero_based_budgeting());
+        self.move_money_button
+            .set_label(&self.model.translation_provider.move_money_button());
+        self.budget_period_button
+            .set_label(&self.model.translation_provider.budget_period_button());
+        self.split_spending_button
+            .set_label(&self.model.translation_provider.split_spending_button());
+        self.tags_report_button
+            .set_label(&self.model.translation_provider.tags_report_button());
+        self.payees_button
+            .set_label(&self.model.translation_provider.payees_button());
+        self.rules_button
+            .set_label(&self.model.translation_provider.rules_button());
+        self.reapply_rules_button
+            .set_label(&self.model.translation_provider.reapply_rules_button());
+        self.accept_suggestions_button
+            .set_label(&self.model.translation_provider.accept_suggestions_button());
+        self.merge_duplicates_button
+            .set_label(&self.model.translation_provider.merge_duplicates_button());
+        self.dismiss_duplicates_button
+            .set_label(&self.model.translation_provider.dismiss_duplicates_button());
+        self.reconcile_button
+            .set_label(&self.model.translation_provider.reconcile_button());
+        self.goals_button
+            .set_label(&self.model.translation_provider.goals_button());
+        self.forecast_button
+            .set_label(&self.model.translation_provider.forecast_button());
+        self.net_worth_button
+            .set_label(&self.model.translation_provider.net_worth_button());
+        self.loans_button
+            .set_label(&self.model.translation_provider.loans_button());
+        self.members_button
+            .set_label(&self.model.translation_provider.members_button());
+        self.settle_up_button
+            .set_label(&self.model.translation_provider.settle_up_button());
+        self.reimbursements_button
+            .set_label(&self.model.translation_provider.reimbursements_button());
+        self.alerts_button
+            .set_label(&self.model.translation_provider.alerts_button());
+        self.bills_button
+            .set_label(&self.model.translation_provider.bills_button());
+        self.spendings_filter_entry.set_placeholder_text(Some(
+            &self
+                .model
+                .translation_provider
+                .spendings_filter_placeholder(),
+        ));
+        self.search_entry
+            .set_placeholder_text(Some(&self.model.translation_provider.search_placeholder()));
+        self.update_reimbursement_combo_box_model();
     }
 
     fn update_monthly_budget_gtk_model_from_moneyz_model(&mut self) {
+        self.model.duplicates = find_duplicates(
+            &self.model.monthly_budget,
+            &self.model.adjacent_monthly_budgets,
+        );
         let spendings_model = get_spendings_model(
             &self.model.monthly_budget,
             &self.model.budget_categories,
+            &self.model.category_classifier,
+            &self.model.duplicates,
+            &self.model.members,
             &self.model.translation_provider,
         );
-        self.spendings_tree_view.set_model(Some(&spendings_model));
-        let tree_model = self.spendings_tree_view.get_model().unwrap();
-        let model = tree_model.downcast::<gtk::ListStore>().unwrap();
-        add_default_spending(&model, self.model.today);
+        add_default_spending(&spendings_model, self.default_spending_date());
+
+        // the sort picked by clicking a header is kept
+        let previous_sort = self
+            .model
+            .spendings_sort
+            .as_ref()
+            .and_then(|sort| sort.get_sort_column_id());
+        let filter_text = self
+            .spendings_filter_entry
+            .get_text()
+            .map_or_else(String::new, |text| text.to_string());
+        let sort = get_sorted_spendings_model(&spendings_model, &filter_text);
+        if let Some((column, order)) = previous_sort {
+            sort.set_sort_column_id(column, order);
+        }
+        self.spendings_tree_view.set_model(Some(&sort));
+        self.spendings_tree_view.expand_all();
+        self.model.spendings_sort = Some(sort);
+    }
+
+    // the row's spending is found by its id, whichever the sort and the filter of the view
+    // the default row stands for the spending after the last one
+    fn view_spending_and_split_rows(&self, path: &gtk::TreePath) -> (usize, Option<usize>) {
+        use SpendingsGtkModelIds::*;
+        let sort = self
+            .model
+            .spendings_sort
+            .as_ref()
+            .expect("The spendings view has no model!");
+        let iter = sort
+            .get_iter(path)
+            .expect("The spendings view is out of sync with its model!");
+        let spendings = &self.model.monthly_budget.spendings;
+        if sort.get_value(&iter, IsDefault.into()).get::<bool>() == Some(true) {
+            return (spendings.0.len(), None);
+        }
+        let spending_id = SpendingId(sort.get_value(&iter, Id.into()).get::<u64>().unwrap());
+        let spending_row = spendings
+            .position(spending_id)
+            .expect("The spendings view is out of sync with its model!");
+        let split_row = if sort.iter_parent(&iter).is_some() {
+            sort.get_value(&iter, SplitIndex.into())
+                .get::<u32>()
+                .map(|split_idx| split_idx as usize)
+        } else {
+            None
+        };
+        (spending_row, split_row)
+    }
+
+    fn on_spendings_filter_changed(&mut self) {
+        self.update_monthly_budget_gtk_model_from_moneyz_model();
     }
 
     fn update_budget_categories_gtk_model_from_moneyz_model(&mut self) {
         let budget_categories_model = get_model_from_budget_categories_and_monthly_budget(
             &self.model.budget_categories,
+            &self.model.budget_category_parents,
             &self.model.monthly_budget,
             &self.model.translation_provider,
+            self.model.config.budgeting_mode,
         );
         self.budget_categories_tree_view
             .set_model(Some(&budget_categories_model));
         let tree_model = self.budget_categories_tree_view.get_model().unwrap();
-        let model = tree_model.downcast::<gtk::ListStore>().unwrap();
+        let model = tree_model.downcast::<gtk::TreeStore>().unwrap();
         add_default_budget_category(&model);
+        self.budget_categories_tree_view.expand_all();
+    }
+
+    fn update_budget_category_combo_boxes_models(&self) {
+        let category_model: gtk::ListStore = (&self.model.budget_categories).into();
+        let tree_model = category_model.upcast::<gtk::TreeModel>();
+        self.model
+            .spending_category_combox_box
+            .as_ref()
+            .unwrap()
+            .set_property_model(Some(&tree_model));
+
+        let parent_model = get_optional_budget_category_model(
+            &self.model.budget_categories,
+            &self.model.translation_provider,
+        );
+        let tree_model = parent_model.upcast::<gtk::TreeModel>();
+        self.model
+            .budget_category_parent_combo_box
+            .as_ref()
+            .unwrap()
+            .set_property_model(Some(&tree_model));
+    }
+
+    // rows can be nested, so the category is found through the hidden id column
+    fn budget_category_id_from_path(&self, path: &gtk::TreePath) -> Option<BudgetCategoryId> {
+        let model = self.budget_categories_tree_view.get_model()?;
+        let iter = model.get_iter(path)?;
+        let id = model
+            .get_value(&iter, BudgetCategoriesTreeStoreIds::Id.into())
+            .get::<u32>()?;
+        if id == NO_BUDGET_CATEGORY_ID {
+            None
+        } else {
+            Some(BudgetCategoryId(id))
+        }
     }
 
     fn update_monthly_total_label_from_moneyz_model(&mut self) {
@@ -876,23 +5331,21 @@ impl Widget for MainWindow {
                 )
                 .unwrap(),
         );
-    }
-
-    fn create_and_fill_month_model(&self) -> gtk::ListStore {
-        let model = gtk::ListStore::new(&[String::static_type()]);
-        for m_idx in 0 as u32..12 {
-            let m: Month = num_traits::FromPrimitive::from_u32(m_idx).unwrap();
-            model.insert_with_values(None, &[0], &[&self.month_to_name(m)]);
-        }
-        model
-    }
 
-    fn create_and_fill_year_model(&self) -> gtk::ListStore {
-        let model = gtk::ListStore::new(&[String::static_type()]);
-        for year in FIRST_YEAR..LAST_YEAR {
-            model.insert_with_values(None, &[0], &[&year.to_string()]);
-        }
-        model
+        let ready_to_assign = MoneyAmount::from_i32(self.model.monthly_budget.ready_to_assign());
+        self.ready_to_assign_label.set_text(
+            &self
+                .model
+                .translation_provider
+                .ready_to_assign(
+                    ready_to_assign.sign(),
+                    ready_to_assign.whole_with_separator(
+                        &self.model.translation_provider.thousands_separator(),
+                    ),
+                    ready_to_assign.cents_padded(),
+                )
+                .unwrap(),
+        );
     }
 
     fn create_and_fill_language_model(&self) -> gtk::ListStore {
@@ -920,3 +5373,192 @@ impl Widget for MainWindow {
         }
     }
 }
+
+// gtk counts the months from 0
+fn calendar_date(calendar: &gtk::Calendar) -> chrono::NaiveDate {
+    let (year, month, day) = calendar.get_date();
+    chrono::NaiveDate::from_ymd(year as i32, month + 1, day)
+}
+
+// the month and spending indexes of the selected line of a reimbursements view
+fn selected_reimbursement_row(tree_view: &gtk::TreeView) -> Option<(usize, usize)> {
+    use ReimbursementsListStoreIds::*;
+    let (model, iter) = tree_view.get_selection().get_selected()?;
+    let month_idx = model
+        .get_value(&iter, MonthIndex.into())
+        .get::<u32>()
+        .unwrap() as usize;
+    let spending_idx = model
+        .get_value(&iter, SpendingIndex.into())
+        .get::<u32>()
+        .unwrap() as usize;
+    Some((month_idx, spending_idx))
+}
+
+// read-only column, for the report dialogs
+fn append_text_column(
+    tree_view: &gtk::TreeView,
+    title: &str,
+    text_column: i32,
+    background_column: Option<i32>,
+) {
+    let col = gtk::TreeViewColumn::new();
+    col.set_title(title);
+    let cell = gtk::CellRendererText::new();
+    col.pack_start(&cell, true);
+    col.add_attribute(&cell, "text", text_column);
+    if let Some(background_column) = background_column {
+        col.add_attribute(&cell, "background", background_column);
+    }
+    tree_view.append_column(&col);
+}
+
+fn scrolled<W: IsA<gtk::Widget>>(widget: &W) -> gtk::ScrolledWindow {
+    let scrolled_window =
+        gtk::ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+    scrolled_window.set_min_content_height(200);
+    scrolled_window.add(widget);
+    scrolled_window
+}
+
+fn payee_id_from_path(tree_view: &gtk::TreeView, path: &gtk::TreePath) -> Option<PayeeId> {
+    let model = tree_view.get_model()?;
+    let iter = model.get_iter(path)?;
+    let id = model
+        .get_value(&iter, PayeesListStoreIds::Id.into())
+        .get::<u32>()?;
+    if id == NO_PAYEE_ID {
+        None
+    } else {
+        Some(PayeeId(id))
+    }
+}
+
+fn append_editable_text_column(
+    tree_view: &gtk::TreeView,
+    title: &str,
+    text_column: i32,
+    background_column: i32,
+) -> gtk::CellRendererText {
+    let col = gtk::TreeViewColumn::new();
+    col.set_title(title);
+    let cell = gtk::CellRendererText::new();
+    cell.set_property_editable(true);
+    col.pack_start(&cell, true);
+    col.add_attribute(&cell, "text", text_column);
+    col.add_attribute(&cell, "background", background_column);
+    tree_view.append_column(&col);
+    cell
+}
+
+// the rows of a dialog's list, as shown in its tree view
+trait DialogRows<T> {
+    fn row_mut(&mut self, row: usize) -> Option<&mut T>;
+    // returns the index of the new row
+    fn push_row(&mut self, new_row: T) -> usize;
+}
+
+impl DialogRows<Rule> for Rules {
+    fn row_mut(&mut self, row: usize) -> Option<&mut Rule> {
+        self.0.get_mut(row)
+    }
+
+    fn push_row(&mut self, new_row: Rule) -> usize {
+        self.0.push(new_row);
+        self.0.len() - 1
+    }
+}
+
+impl DialogRows<SavingsGoal> for Goals {
+    fn row_mut(&mut self, row: usize) -> Option<&mut SavingsGoal> {
+        self.0.get_mut(row)
+    }
+
+    fn push_row(&mut self, new_row: SavingsGoal) -> usize {
+        self.0.push(new_row);
+        self.0.len() - 1
+    }
+}
+
+impl DialogRows<Bill> for Bills {
+    fn row_mut(&mut self, row: usize) -> Option<&mut Bill> {
+        self.0.get_mut(row)
+    }
+
+    fn push_row(&mut self, new_row: Bill) -> usize {
+        self.0.push(new_row);
+        self.0.len() - 1
+    }
+}
+
+impl DialogRows<Loan> for Loans {
+    fn row_mut(&mut self, row: usize) -> Option<&mut Loan> {
+        self.0.get_mut(row)
+    }
+
+    fn push_row(&mut self, new_row: Loan) -> usize {
+        self.0.push(new_row);
+        self.0.len() - 1
+    }
+}
+
+impl DialogRows<Valuation> for Valuations {
+    fn row_mut(&mut self, row: usize) -> Option<&mut Valuation> {
+        self.valuations.get_mut(row)
+    }
+
+    fn push_row(&mut self, new_row: Valuation) -> usize {
+        self.valuations.push(new_row);
+        self.valuations.len() - 1
+    }
+}
+
+// the holdings are listed by id, a new holding getting the highest one
+impl DialogRows<Holding> for Valuations {
+    fn row_mut(&mut self, row: usize) -> Option<&mut Holding> {
+        self.holdings.values_mut().nth(row)
+    }
+
+    fn push_row(&mut self, new_row: Holding) -> usize {
+        let holding_id = self.add_holding(new_row.name);
+        if let Some(holding) = self.holdings.get_mut(&holding_id) {
+            holding.kind = new_row.kind;
+        }
+        self.holdings.len() - 1
+    }
+}
+
+// editing the default row (i.e. the one after the last) creates a new row
+// the row is updated on a copy, so that update_row can look at the other rows
+fn connect_row_cell_edited<C, R, T, N, F>(
+    cell: &C,
+    rows: &Rc<RefCell<R>>,
+    refresh: &Rc<dyn Fn()>,
+    new_row: N,
+    update_row: F,
+) where
+    C: IsA<gtk::CellRendererText>,
+    R: DialogRows<T> + 'static,
+    T: Clone,
+    N: Fn(&R) -> T + 'static,
+    F: Fn(&mut T, &str) + 'static,
+{
+    let rows = rows.clone();
+    let refresh = refresh.clone();
+    cell.connect_edited(move |_, path, value| {
+        let row = path.get_indices()[0] as usize;
+        let existing_row = rows.borrow_mut().row_mut(row).map(|row| row.clone());
+        let (row, mut edited_row) = match existing_row {
+            Some(edited_row) => (row, edited_row),
+            None => {
+                let edited_row = new_row(&rows.borrow());
+                (rows.borrow_mut().push_row(edited_row.clone()), edited_row)
+            }
+        };
+        update_row(&mut edited_row, value);
+        if let Some(row) = rows.borrow_mut().row_mut(row) {
+            *row = edited_row;
+        }
+        refresh();
+    });
+}
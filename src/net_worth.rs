@@ -0,0 +1,150 @@
+use crate::data::{date_in_month, month_year_of, next_month};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Hash, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HoldingId(pub u32);
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum HoldingKind {
+    Account,
+    Property,
+    Investment,
+    Loan,
+    OtherLiability,
+}
+
+// in the order they are offered in the combo boxes
+pub const HOLDING_KINDS: [HoldingKind; 5] = [
+    HoldingKind::Account,
+    HoldingKind::Property,
+    HoldingKind::Investment,
+    HoldingKind::Loan,
+    HoldingKind::OtherLiability,
+];
+
+impl HoldingKind {
+    pub fn is_liability(self) -> bool {
+        match self {
+            HoldingKind::Loan | HoldingKind::OtherLiability => true,
+            HoldingKind::Account | HoldingKind::Property | HoldingKind::Investment => false,
+        }
+    }
+}
+
+// an account, a house, a mortgage...
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Holding {
+    pub name: String,
+    pub kind: HoldingKind,
+}
+
+// what a holding was worth on a given date, in cents
+// liabilities are valued with what is owed, as a positive amount
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Valuation {
+    pub holding_id: HoldingId,
+    pub date: NaiveDate,
+    pub value: i32,
+}
+
+// same as the Payees, new ids are the max one + 1
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Valuations {
+    pub holdings: BTreeMap<HoldingId, Holding>,
+    // sorted by date
+    pub valuations: Vec<Valuation>,
+}
+
+impl Valuations {
+    pub fn add_holding(&mut self, name: String) -> HoldingId {
+        let new_id = HoldingId(self.holdings.keys().last().map_or(0, |id| id.0 + 1));
+        self.holdings.insert(
+            new_id,
+            Holding {
+                name,
+                kind: HoldingKind::Account,
+            },
+        );
+        new_id
+    }
+
+    // its valuations go with it
+    pub fn remove_holding(&mut self, holding_id: HoldingId) {
+        self.holdings.remove(&holding_id);
+        self.valuations
+            .retain(|valuation| valuation.holding_id != holding_id);
+    }
+
+    pub fn sort_by_date(&mut self) {
+        self.valuations
+            .sort_by_key(|valuation| (valuation.date, valuation.holding_id));
+    }
+
+    // the latest valuation up to the date
+    pub fn value_at(&self, holding_id: HoldingId, date: NaiveDate) -> Option<i32> {
+        self.valuations
+            .iter()
+            .filter(|valuation| valuation.holding_id == holding_id && valuation.date <= date)
+            .max_by_key(|valuation| valuation.date)
+            .map(|valuation| valuation.value)
+    }
+
+    pub fn net_worth_at(&self, date: NaiveDate) -> NetWorthPoint {
+        let mut point = NetWorthPoint {
+            date,
+            assets: 0,
+            liabilities: 0,
+        };
+        for (holding_id, holding) in &self.holdings {
+            let value = self.value_at(*holding_id, date).unwrap_or(0);
+            if holding.kind.is_liability() {
+                point.liabilities += value;
+            } else {
+                point.assets += value;
+            }
+        }
+        point
+    }
+}
+
+pub struct NetWorthPoint {
+    pub date: NaiveDate,
+    pub assets: i32,
+    pub liabilities: i32,
+}
+
+impl NetWorthPoint {
+    pub fn net_worth(&self) -> i32 {
+        self.assets - self.liabilities
+    }
+}
+
+// one point at the end of every month since the first valuation, the last one being today
+pub fn net_worth_over_time(valuations: &Valuations, today: NaiveDate) -> Vec<NetWorthPoint> {
+    let first_date = match valuations
+        .valuations
+        .iter()
+        .map(|valuation| valuation.date)
+        .min()
+    {
+        Some(first_date) => first_date,
+        None => return Vec::new(),
+    };
+
+    let mut points = Vec::new();
+    let (mut m, mut y) = month_year_of(first_date);
+    loop {
+        let end_of_month = date_in_month(m, y, 31);
+        if end_of_month >= today {
+            points.push(valuations.net_worth_at(today));
+            break;
+        }
+        points.push(valuations.net_worth_at(end_of_month));
+        let next = next_month(m, y);
+        m = next.0;
+        y = next.1;
+    }
+    points
+}
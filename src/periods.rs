@@ -0,0 +1,216 @@
+use crate::data::{date_in_month, month_year_of, next_month, previous_month, Month, Year};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// how the time is cut into budget periods
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum BudgetPeriodKind {
+    CalendarMonth,
+    // e.g. from the 25th to the 24th of the next month, for those paid on the 25th
+    MonthStartingOn { day: u32 },
+    // the anchor is the first day of any of the periods
+    Weekly { anchor: NaiveDate },
+    BiWeekly { anchor: NaiveDate },
+}
+
+impl Default for BudgetPeriodKind {
+    fn default() -> Self {
+        BudgetPeriodKind::CalendarMonth
+    }
+}
+
+// both ends are included
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BudgetPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl BudgetPeriodKind {
+    pub fn period_containing(&self, date: NaiveDate) -> BudgetPeriod {
+        match *self {
+            BudgetPeriodKind::CalendarMonth => {
+                BudgetPeriodKind::MonthStartingOn { day: 1 }.period_containing(date)
+            }
+            BudgetPeriodKind::MonthStartingOn { day } => {
+                let (m, y) = month_year_of(date);
+                let start_this_month = date_in_month(m, y, day);
+                let (start_m, start_y) = if date >= start_this_month {
+                    (m, y)
+                } else {
+                    previous_month(m, y)
+                };
+                let (end_m, end_y) = next_month(start_m, start_y);
+                BudgetPeriod {
+                    start: date_in_month(start_m, start_y, day),
+                    end: date_in_month(end_m, end_y, day) - Duration::days(1),
+                }
+            }
+            BudgetPeriodKind::Weekly { anchor } => period_of_fixed_length(anchor, 7, date),
+            BudgetPeriodKind::BiWeekly { anchor } => period_of_fixed_length(anchor, 14, date),
+        }
+    }
+
+    pub fn next(&self, period: &BudgetPeriod) -> BudgetPeriod {
+        self.period_containing(period.end + Duration::days(1))
+    }
+
+    pub fn previous(&self, period: &BudgetPeriod) -> BudgetPeriod {
+        self.period_containing(period.start - Duration::days(1))
+    }
+}
+
+fn period_of_fixed_length(anchor: NaiveDate, length: i64, date: NaiveDate) -> BudgetPeriod {
+    let periods_since_anchor = (date - anchor).num_days().div_euclid(length);
+    let start = anchor + Duration::days(periods_since_anchor * length);
+    BudgetPeriod {
+        start,
+        end: start + Duration::days(length - 1),
+    }
+}
+
+impl BudgetPeriod {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    // the calendar months are what the old files were made of, they keep the same layout
+    pub fn as_calendar_month(&self) -> Option<(Month, Year)> {
+        let (m, y) = month_year_of(self.start);
+        let (next_m, next_y) = next_month(m, y);
+        if self.start.day() == 1 && self.end + Duration::days(1) == date_in_month(next_m, next_y, 1)
+        {
+            Some((m, y))
+        } else {
+            None
+        }
+    }
+
+    // the month files the spendings of the period are stored in
+    pub fn months(&self) -> Vec<(Month, Year)> {
+        let mut months = vec![month_year_of(self.start)];
+        let (last_m, last_y) = month_year_of(self.end);
+        loop {
+            let (m, y) = *months.last().unwrap();
+            if m == last_m && y == last_y {
+                break;
+            }
+            months.push(next_month(m, y));
+        }
+        months
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd(y, m, d)
+    }
+
+    // every day from the start belongs to exactly one period, the periods following each other
+    fn assert_tiles(kind: BudgetPeriodKind, from: NaiveDate, days: i64) {
+        let mut period = kind.period_containing(from);
+        assert!(period.contains(from));
+        for offset in 0..days {
+            let day = from + Duration::days(offset);
+            if !period.contains(day) {
+                let next = kind.next(&period);
+                assert_eq!(next.start, period.end + Duration::days(1));
+                assert!(next.start <= next.end);
+                assert_eq!(kind.previous(&next), period);
+                period = next;
+            }
+            assert_eq!(kind.period_containing(day), period);
+        }
+    }
+
+    #[test]
+    fn month_starting_on_31_clamps_to_short_months() {
+        let kind = BudgetPeriodKind::MonthStartingOn { day: 31 };
+        assert_eq!(
+            kind.period_containing(date(2021, 2, 10)),
+            BudgetPeriod {
+                start: date(2021, 1, 31),
+                end: date(2021, 2, 27),
+            }
+        );
+        assert_eq!(
+            kind.period_containing(date(2021, 2, 28)),
+            BudgetPeriod {
+                start: date(2021, 2, 28),
+                end: date(2021, 3, 30),
+            }
+        );
+        assert_tiles(kind, date(2020, 12, 1), 800);
+    }
+
+    #[test]
+    fn month_starting_on_29_in_a_leap_year() {
+        let kind = BudgetPeriodKind::MonthStartingOn { day: 29 };
+        assert_eq!(
+            kind.period_containing(date(2020, 2, 29)),
+            BudgetPeriod {
+                start: date(2020, 2, 29),
+                end: date(2020, 3, 28),
+            }
+        );
+        assert_eq!(
+            kind.period_containing(date(2020, 2, 28)),
+            BudgetPeriod {
+                start: date(2020, 1, 29),
+                end: date(2020, 2, 28),
+            }
+        );
+        assert_tiles(kind, date(2019, 12, 1), 800);
+        assert_tiles(BudgetPeriodKind::CalendarMonth, date(2019, 12, 1), 800);
+    }
+
+    #[test]
+    fn fixed_length_periods_before_the_anchor() {
+        let anchor = date(2020, 1, 6);
+        let kind = BudgetPeriodKind::Weekly { anchor };
+        assert_eq!(
+            kind.period_containing(date(2020, 1, 5)),
+            BudgetPeriod {
+                start: date(2019, 12, 30),
+                end: date(2020, 1, 5),
+            }
+        );
+        assert_tiles(kind, date(2019, 11, 1), 120);
+
+        let kind = BudgetPeriodKind::BiWeekly { anchor };
+        assert_eq!(
+            kind.period_containing(date(2019, 12, 23)),
+            BudgetPeriod {
+                start: date(2019, 12, 23),
+                end: date(2020, 1, 5),
+            }
+        );
+        assert_tiles(kind, date(2019, 11, 1), 120);
+    }
+
+    #[test]
+    fn months_of_a_period_across_the_year() {
+        let period =
+            BudgetPeriodKind::MonthStartingOn { day: 25 }.period_containing(date(2020, 1, 3));
+        assert_eq!(
+            period,
+            BudgetPeriod {
+                start: date(2019, 12, 25),
+                end: date(2020, 1, 24),
+            }
+        );
+        assert_eq!(
+            period.months(),
+            vec![(Month::Dec, Year(2019)), (Month::Jan, Year(2020))]
+        );
+        assert_eq!(period.as_calendar_month(), None);
+
+        let period = BudgetPeriodKind::CalendarMonth.period_containing(date(2020, 12, 31));
+        assert_eq!(period.months(), vec![(Month::Dec, Year(2020))]);
+        assert_eq!(period.as_calendar_month(), Some((Month::Dec, Year(2020))));
+    }
+}
@@ -0,0 +1,20 @@
+// the names between braces, "{{" and "}}" being escaped braces
+// build.rs includes this file as well, to check the built in translation files
+pub fn placeholders(format: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        let placeholder = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+        // the formatting spec isn't part of the name
+        let name = placeholder.split(':').next().unwrap_or_default();
+        placeholders.push(name.trim().to_owned());
+    }
+    placeholders
+}
@@ -0,0 +1,32 @@
+use crate::data::{ClearedStatus, Month, MonthlyBudget, Year};
+use chrono::NaiveDate;
+
+// what the bank should show at the end date: the sum of every cleared spending until then
+pub fn cleared_balance(monthly_budgets: &[(Month, Year, MonthlyBudget)], end: NaiveDate) -> i32 {
+    monthly_budgets
+        .iter()
+        .flat_map(|(_, _, monthly_budget)| &monthly_budget.spendings.0)
+        .filter(|spending| spending.status != ClearedStatus::Uncleared && spending.date <= end)
+        .map(|spending| spending.amount.to_i32())
+        .sum()
+}
+
+// the cleared spendings until the end date become reconciled; returns the index of the months
+// which have changed
+pub fn reconcile(
+    monthly_budgets: &mut [(Month, Year, MonthlyBudget)],
+    end: NaiveDate,
+) -> Vec<usize> {
+    let mut changed_months = Vec::new();
+    for (idx, (_, _, monthly_budget)) in monthly_budgets.iter_mut().enumerate() {
+        for spending in &mut monthly_budget.spendings.0 {
+            if spending.status == ClearedStatus::Cleared && spending.date <= end {
+                spending.status = ClearedStatus::Reconciled;
+                if !changed_months.contains(&idx) {
+                    changed_months.push(idx);
+                }
+            }
+        }
+    }
+    changed_months
+}
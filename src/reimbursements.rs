@@ -0,0 +1,82 @@
+use crate::data::{
+    MoneyAmount, Month, MonthlyBudget, ReimbursementStatus, Spending, Year, DATE_FORMAT,
+};
+
+// a work expense which hasn't been paid back yet
+pub fn is_outstanding(spending: &Spending) -> bool {
+    match spending.reimbursement {
+        Some(ReimbursementStatus::Pending) | Some(ReimbursementStatus::Submitted) => true,
+        Some(ReimbursementStatus::Reimbursed) | None => false,
+    }
+}
+
+// a credit which could be the payment of an expense claim
+pub fn is_unlinked_income(spending: &Spending) -> bool {
+    spending.amount.to_i32() > 0
+        && spending.reimbursement.is_none()
+        && spending.reimbursement_link.is_none()
+}
+
+pub fn next_link_id(monthly_budgets: &[(Month, Year, MonthlyBudget)]) -> u32 {
+    monthly_budgets
+        .iter()
+        .flat_map(|(_, _, monthly_budget)| &monthly_budget.spendings.0)
+        .filter_map(|spending| spending.reimbursement_link)
+        .max()
+        .map_or(0, |link_id| link_id + 1)
+}
+
+// the income goes to the expense's category, so that they net to zero in its balance
+pub fn link(expense: &mut Spending, income: &mut Spending, link_id: u32) {
+    expense.reimbursement = Some(ReimbursementStatus::Reimbursed);
+    expense.reimbursement_link = Some(link_id);
+    income.reimbursement_link = Some(link_id);
+    income.budget_category_id = expense.budget_category_id;
+    income.budget_category_name = expense.budget_category_name.clone();
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+// one line per expense, with what is claimed as a positive amount
+pub fn to_csv<F>(headers: &[String], expenses: &[&Spending], status_name: F) -> String
+where
+    F: Fn(ReimbursementStatus) -> String,
+{
+    let mut csv = headers
+        .iter()
+        .map(|header| escape_csv(header))
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for expense in expenses {
+        let claimed = MoneyAmount::from_i32(-expense.amount.to_i32());
+        let fields = [
+            expense.date.format(DATE_FORMAT).to_string(),
+            expense.name.clone(),
+            expense.budget_category_name.0.clone(),
+            format!(
+                "{}{}.{}",
+                claimed.sign(),
+                claimed.whole_with_separator(""),
+                claimed.cents_padded()
+            ),
+            expense.reimbursement.map_or_else(String::new, &status_name),
+            expense.note.clone(),
+        ];
+        csv.push_str(
+            &fields
+                .iter()
+                .map(|field| escape_csv(field))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        csv.push('\n');
+    }
+    csv
+}
@@ -0,0 +1,62 @@
+use crate::data::{normalize_tag, Month, MonthlyBudget, Year, DATE_FORMAT};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+// a copy of the parts of a spending we need for the reports, so that they don't have to keep
+// every month loaded
+pub struct TaggedSpending {
+    pub date: NaiveDate,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub amount: i32,
+}
+
+impl TaggedSpending {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = normalize_tag(tag);
+        self.tags.iter().any(|spending_tag| *spending_tag == tag)
+    }
+
+    pub fn date_to_string(&self) -> String {
+        self.date.format(DATE_FORMAT).to_string()
+    }
+}
+
+pub fn collect_tagged_spendings(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+) -> Vec<TaggedSpending> {
+    let mut tagged_spendings = Vec::new();
+    for (_, _, monthly_budget) in monthly_budgets {
+        for spending in &monthly_budget.spendings.0 {
+            if spending.tags.is_empty() {
+                continue;
+            }
+            tagged_spendings.push(TaggedSpending {
+                date: spending.date,
+                name: spending.name.clone(),
+                tags: spending.tags.clone(),
+                amount: spending.amount.to_i32(),
+            });
+        }
+    }
+    tagged_spendings
+}
+
+// a spending with several tags counts in the total of each of them
+pub fn tag_totals(tagged_spendings: &[TaggedSpending]) -> BTreeMap<String, i32> {
+    let mut totals = BTreeMap::new();
+    for tagged_spending in tagged_spendings {
+        for tag in &tagged_spending.tags {
+            *totals.entry(tag.clone()).or_insert(0) += tagged_spending.amount;
+        }
+    }
+    totals
+}
+
+pub fn total_for_tag(tagged_spendings: &[TaggedSpending], tag: &str) -> i32 {
+    tagged_spendings
+        .iter()
+        .filter(|tagged_spending| tagged_spending.has_tag(tag))
+        .map(|tagged_spending| tagged_spending.amount)
+        .sum()
+}
@@ -0,0 +1,129 @@
+use crate::data::{BudgetCategories, BudgetCategoryId, Day, Spending};
+use chrono::Datelike;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+// every condition which is set must match for the rule to apply
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Rule {
+    // the lowest priority is evaluated first
+    pub priority: i32,
+    #[serde(default)]
+    pub name_pattern: Option<String>,
+    // both bounds are inclusive, in cents
+    #[serde(default)]
+    pub min_amount: Option<i32>,
+    #[serde(default)]
+    pub max_amount: Option<i32>,
+    #[serde(default)]
+    pub day: Option<Day>,
+    #[serde(default)]
+    pub budget_category_id: Option<BudgetCategoryId>,
+}
+
+impl Rule {
+    pub fn new(priority: i32) -> Self {
+        Rule {
+            priority,
+            name_pattern: None,
+            min_amount: None,
+            max_amount: None,
+            day: None,
+            budget_category_id: None,
+        }
+    }
+
+    // an invalid pattern makes the rule never match
+    pub fn is_name_pattern_valid(&self) -> bool {
+        self.name_pattern
+            .as_ref()
+            .map_or(true, |pattern| Regex::new(pattern).is_ok())
+    }
+
+    pub fn matches(&self, spending: &Spending) -> bool {
+        if let Some(pattern) = &self.name_pattern {
+            match Regex::new(pattern) {
+                Ok(regex) => {
+                    if !regex.is_match(&spending.name) {
+                        return false;
+                    }
+                }
+                Err(_) => return false,
+            }
+        }
+        let amount = spending.amount.to_i32();
+        if self
+            .min_amount
+            .map_or(false, |min_amount| amount < min_amount)
+        {
+            return false;
+        }
+        if self
+            .max_amount
+            .map_or(false, |max_amount| amount > max_amount)
+        {
+            return false;
+        }
+        if self
+            .day
+            .map_or(false, |day| day.0 as u32 != spending.date.day())
+        {
+            return false;
+        }
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Rules(pub Vec<Rule>);
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules(Vec::new())
+    }
+}
+
+impl Rules {
+    // stable, so rules with the same priority keep the order they were created in
+    pub fn sort_by_priority(&mut self) {
+        self.0.sort_by_key(|rule| rule.priority);
+    }
+
+    pub fn next_priority(&self) -> i32 {
+        self.0
+            .iter()
+            .map(|rule| rule.priority)
+            .max()
+            .map_or(0, |p| p + 1)
+    }
+
+    // the category of the first matching rule, by priority
+    pub fn categorize(&self, spending: &Spending) -> Option<BudgetCategoryId> {
+        let mut rules = self.0.iter().collect::<Vec<_>>();
+        rules.sort_by_key(|rule| rule.priority);
+        rules
+            .into_iter()
+            .filter(|rule| rule.budget_category_id.is_some())
+            .find(|rule| rule.matches(spending))
+            .and_then(|rule| rule.budget_category_id)
+    }
+
+    // used for typed spendings as well as imported ones; returns true if the category changed
+    pub fn apply(&self, spending: &mut Spending, budget_categories: &BudgetCategories) -> bool {
+        let budget_category_id = match self.categorize(spending) {
+            Some(budget_category_id) => budget_category_id,
+            None => return false,
+        };
+        let budget_category_name = match budget_categories.0.get(&budget_category_id) {
+            Some(budget_category_name) => budget_category_name,
+            // the rule points to a deleted category
+            None => return false,
+        };
+        if spending.budget_category_id == budget_category_id {
+            return false;
+        }
+        spending.budget_category_id = budget_category_id;
+        spending.budget_category_name = budget_category_name.clone();
+        true
+    }
+}
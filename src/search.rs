@@ -0,0 +1,84 @@
+use crate::data::{BudgetCategoryId, Month, MonthlyBudget, Spending, Year};
+use chrono::NaiveDate;
+use regex::{Regex, RegexBuilder};
+
+// every criterion which is set must match
+#[derive(Debug, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    // the text is a regular expression instead of a plain substring
+    pub use_regex: bool,
+    pub budget_category_id: Option<BudgetCategoryId>,
+    // both bounds are inclusive, in cents, and compared to the amount without its sign so that
+    // "between 20 and 50" finds purchases as well as refunds
+    pub min_amount: Option<i32>,
+    pub max_amount: Option<i32>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+enum NameMatcher {
+    Any,
+    Substring(String),
+    Regex(Regex),
+}
+
+impl NameMatcher {
+    fn is_match(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Any => true,
+            NameMatcher::Substring(text) => name.to_lowercase().contains(text),
+            NameMatcher::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl SearchQuery {
+    fn matches(&self, spending: &Spending, name_matcher: &NameMatcher) -> bool {
+        let amount = spending.amount.to_i32().abs();
+        name_matcher.is_match(&spending.name)
+            && self.budget_category_id.map_or(true, |budget_category_id| {
+                spending
+                    .amounts_per_category()
+                    .iter()
+                    .any(|(id, _)| *id == budget_category_id)
+            })
+            && self
+                .min_amount
+                .map_or(true, |min_amount| amount >= min_amount)
+            && self
+                .max_amount
+                .map_or(true, |max_amount| amount <= max_amount)
+            && self.from.map_or(true, |from| spending.date >= from)
+            && self.to.map_or(true, |to| spending.date <= to)
+    }
+}
+
+// the month and spending indexes of the matching spendings, the most recent first; the regex
+// error is returned as is, to be shown while it's being typed
+pub fn search(
+    monthly_budgets: &[(Month, Year, MonthlyBudget)],
+    query: &SearchQuery,
+) -> Result<Vec<(usize, usize)>, regex::Error> {
+    let text = query.text.trim();
+    let name_matcher = if text.is_empty() {
+        NameMatcher::Any
+    } else if query.use_regex {
+        NameMatcher::Regex(RegexBuilder::new(text).case_insensitive(true).build()?)
+    } else {
+        NameMatcher::Substring(text.to_lowercase())
+    };
+
+    let mut matches = Vec::new();
+    for (month_idx, (_, _, monthly_budget)) in monthly_budgets.iter().enumerate() {
+        for (spending_idx, spending) in monthly_budget.spendings.0.iter().enumerate() {
+            if query.matches(spending, &name_matcher) {
+                matches.push((month_idx, spending_idx));
+            }
+        }
+    }
+    matches.sort_by_key(|(month_idx, spending_idx)| {
+        std::cmp::Reverse(monthly_budgets[*month_idx].2.spendings.0[*spending_idx].date)
+    });
+    Ok(matches)
+}
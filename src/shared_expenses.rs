@@ -0,0 +1,334 @@
+use crate::data::{MemberId, Members, MoneyAmount, ShareRule, Spending};
+use std::collections::BTreeMap;
+
+// "Alice 60%, Bob 40%" or "Alice 12.50, Bob 7.50", an empty text (or the equal word) meaning
+// an equal share
+pub fn parse_share_rule(
+    text: &str,
+    members: &Members,
+    equal_word: &str,
+    decimal_separator: &str,
+) -> Option<ShareRule> {
+    let text = text.trim();
+    if text.is_empty() || text.to_lowercase() == equal_word.to_lowercase() {
+        return Some(ShareRule::Equal);
+    }
+
+    let mut percentages = Vec::new();
+    let mut exact_amounts = Vec::new();
+    for part in text.split(',') {
+        let part = part.trim();
+        let separator_idx = part.rfind(char::is_whitespace)?;
+        let member_id = members.find(&part[..separator_idx])?;
+        let value = part[separator_idx..].trim();
+        if value.ends_with('%') {
+            let percentage = value
+                .trim_end_matches('%')
+                .trim()
+                .replace(decimal_separator, ".")
+                .parse::<f64>()
+                .ok()?;
+            percentages.push((member_id, percentage));
+        } else {
+            let amount = MoneyAmount::from_string(value, decimal_separator)?;
+            exact_amounts.push((member_id, amount.to_i32()));
+        }
+    }
+    // both kinds can't be mixed
+    match (percentages.is_empty(), exact_amounts.is_empty()) {
+        (false, true) => Some(ShareRule::Percentages(percentages)),
+        (true, false) => Some(ShareRule::ExactAmounts(exact_amounts)),
+        _ => None,
+    }
+}
+
+// what each member owes for the spending, the cents which can't be split go to the payer
+pub fn shares(spending: &Spending, members: &Members) -> Vec<(MemberId, i32)> {
+    let payer = match spending.paid_by {
+        Some(payer) => payer,
+        None => return Vec::new(),
+    };
+    // an expense is a debit, the credits (e.g. refunds) are shared the same way
+    let cost = -spending.amount.to_i32();
+    let mut shares = match &spending.share_rule {
+        ShareRule::Equal => {
+            let count = members.0.len() as i32;
+            if count == 0 {
+                return Vec::new();
+            }
+            members
+                .0
+                .keys()
+                .map(|member_id| (*member_id, cost / count))
+                .collect::<Vec<_>>()
+        }
+        ShareRule::Percentages(percentages) => percentages
+            .iter()
+            .map(|(member_id, percentage)| {
+                (
+                    *member_id,
+                    (f64::from(cost) * percentage / 100.0).round() as i32,
+                )
+            })
+            .collect(),
+        ShareRule::ExactAmounts(exact_amounts) => exact_amounts.clone(),
+    };
+
+    let leftover = cost - shares.iter().map(|(_, share)| share).sum::<i32>();
+    if leftover != 0 {
+        match shares.iter_mut().find(|(member_id, _)| *member_id == payer) {
+            Some((_, share)) => *share += leftover,
+            None => shares.push((payer, leftover)),
+        }
+    }
+    shares
+}
+
+// positive: the member is owed money, negative: the member owes money
+pub fn balances<'a, I>(spendings: I, members: &Members) -> BTreeMap<MemberId, i32>
+where
+    I: IntoIterator<Item = &'a Spending>,
+{
+    let mut balances = members
+        .0
+        .keys()
+        .map(|member_id| (*member_id, 0))
+        .collect::<BTreeMap<_, _>>();
+    for spending in spendings {
+        let payer = match spending.paid_by {
+            Some(payer) if members.0.contains_key(&payer) => payer,
+            _ => continue,
+        };
+        *balances.entry(payer).or_insert(0) -= spending.amount.to_i32();
+        for (member_id, share) in shares(spending, members) {
+            *balances.entry(member_id).or_insert(0) -= share;
+        }
+    }
+    balances
+}
+
+pub struct Settlement {
+    pub from: MemberId,
+    pub to: MemberId,
+    pub amount: i32,
+}
+
+// above that, going through the subsets takes too long and the members are settled as one group
+const MAX_MEMBERS_TO_GROUP: usize = 16;
+
+// the members whose balances sum to zero can settle between themselves, a group of n members
+// taking n - 1 transfers: splitting the members into the most groups gives the fewest transfers
+pub fn settle_up(balances: &BTreeMap<MemberId, i32>) -> Vec<Settlement> {
+    let members = balances
+        .iter()
+        .filter(|(_, balance)| **balance != 0)
+        .map(|(member_id, balance)| (*member_id, *balance))
+        .collect::<Vec<_>>();
+    zero_sum_groups(&members)
+        .iter()
+        .flat_map(|group| settle_group(group))
+        .collect()
+}
+
+// the subsets are bit masks of the members, the groups are found along a path which removes one
+// member at a time, and which goes through the most subsets summing to zero
+fn zero_sum_groups(members: &[(MemberId, i32)]) -> Vec<Vec<(MemberId, i32)>> {
+    if members.len() > MAX_MEMBERS_TO_GROUP {
+        return vec![members.to_vec()];
+    }
+    let all_members = (1usize << members.len()) - 1;
+    let mut sums = vec![0i64; all_members + 1];
+    let mut group_counts = vec![0u32; all_members + 1];
+    let is_zero_sum = |sums: &[i64], subset: usize| if sums[subset] == 0 { 1 } else { 0 };
+    for subset in 1..=all_members {
+        let lowest = subset.trailing_zeros() as usize;
+        sums[subset] = sums[subset & (subset - 1)] + i64::from(members[lowest].1);
+        group_counts[subset] = (0..members.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .map(|i| group_counts[subset ^ (1 << i)])
+            .max()
+            .unwrap_or(0)
+            + is_zero_sum(&sums, subset);
+    }
+
+    let mut groups = Vec::new();
+    let mut subset = all_members;
+    let mut group_end = all_members;
+    while subset != 0 {
+        let removed = (0..members.len())
+            .find(|i| {
+                subset & (1 << i) != 0
+                    && group_counts[subset ^ (1 << i)] + is_zero_sum(&sums, subset)
+                        == group_counts[subset]
+            })
+            .expect("A subset's count comes from one of its members");
+        subset ^= 1 << removed;
+        if subset == 0 || sums[subset] == 0 {
+            let group = group_end ^ subset;
+            groups.push(
+                (0..members.len())
+                    .filter(|i| group & (1 << i) != 0)
+                    .map(|i| members[i])
+                    .collect(),
+            );
+            group_end = subset;
+        }
+    }
+    groups
+}
+
+// within a group, the largest debtor pays the largest creditor until everyone is even, which
+// takes at most one transfer less than there are members
+fn settle_group(members: &[(MemberId, i32)]) -> Vec<Settlement> {
+    let mut debtors = members
+        .iter()
+        .filter(|(_, balance)| *balance < 0)
+        .map(|(member_id, balance)| (*member_id, -*balance))
+        .collect::<Vec<_>>();
+    let mut creditors = members
+        .iter()
+        .filter(|(_, balance)| *balance > 0)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut settlements = Vec::new();
+    loop {
+        debtors.sort_by_key(|(_, amount)| -*amount);
+        creditors.sort_by_key(|(_, amount)| -*amount);
+        let (debtor, creditor) = match (debtors.first_mut(), creditors.first_mut()) {
+            (Some(debtor), Some(creditor)) => (debtor, creditor),
+            _ => break,
+        };
+        let amount = debtor.1.min(creditor.1);
+        if amount == 0 {
+            break;
+        }
+        settlements.push(Settlement {
+            from: debtor.0,
+            to: creditor.0,
+            amount,
+        });
+        debtor.1 -= amount;
+        creditor.1 -= amount;
+        debtors.retain(|(_, amount)| *amount > 0);
+        creditors.retain(|(_, amount)| *amount > 0);
+    }
+    settlements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{BudgetCategory, BudgetCategoryId, Member, SpendingId};
+    use chrono::NaiveDate;
+
+    fn members(count: u32) -> Members {
+        Members(
+            (0..count)
+                .map(|id| {
+                    (
+                        MemberId(id),
+                        Member {
+                            name: format!("member {}", id),
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn spending(amount: i32, paid_by: MemberId, share_rule: ShareRule) -> Spending {
+        Spending {
+            id: SpendingId::new(),
+            name: String::from("spending"),
+            budget_category_id: BudgetCategoryId(0),
+            budget_category_name: BudgetCategory(String::from("category")),
+            amount: MoneyAmount::from_i32(amount),
+            date: NaiveDate::from_ymd(2020, 1, 1),
+            legacy_day: None,
+            splits: Vec::new(),
+            tags: Vec::new(),
+            note: String::new(),
+            dismissed_duplicates: Vec::new(),
+            status: Default::default(),
+            paid_by: Some(paid_by),
+            share_rule,
+            reimbursement: None,
+            reimbursement_link: None,
+        }
+    }
+
+    #[test]
+    fn equal_shares_leftover_goes_to_the_payer() {
+        let shares = shares(&spending(-1000, MemberId(1), ShareRule::Equal), &members(3));
+        assert_eq!(
+            shares,
+            vec![(MemberId(0), 333), (MemberId(1), 334), (MemberId(2), 333)]
+        );
+    }
+
+    #[test]
+    fn rounded_percentages_leftover_goes_to_the_payer() {
+        let shares = shares(
+            &spending(
+                -1001,
+                MemberId(0),
+                ShareRule::Percentages(vec![(MemberId(0), 50.0), (MemberId(1), 50.0)]),
+            ),
+            &members(2),
+        );
+        assert_eq!(shares, vec![(MemberId(0), 500), (MemberId(1), 501)]);
+    }
+
+    #[test]
+    fn exact_amounts_leftover_goes_to_the_payer() {
+        let shares = shares(
+            &spending(
+                -1000,
+                MemberId(0),
+                ShareRule::ExactAmounts(vec![(MemberId(1), 600)]),
+            ),
+            &members(2),
+        );
+        assert_eq!(shares, vec![(MemberId(1), 600), (MemberId(0), 400)]);
+    }
+
+    fn settled(balances: &BTreeMap<MemberId, i32>) -> usize {
+        let settlements = settle_up(balances);
+        let mut balances = balances.clone();
+        for settlement in &settlements {
+            assert!(settlement.amount > 0);
+            *balances.get_mut(&settlement.from).unwrap() += settlement.amount;
+            *balances.get_mut(&settlement.to).unwrap() -= settlement.amount;
+        }
+        assert!(balances.values().all(|balance| *balance == 0));
+        settlements.len()
+    }
+
+    #[test]
+    fn settle_up_nothing_to_settle() {
+        let balances = (0..3).map(|id| (MemberId(id), 0)).collect();
+        assert_eq!(settled(&balances), 0);
+    }
+
+    #[test]
+    fn settle_up_uses_the_fewest_transfers() {
+        // paying the largest creditor first would take 4 transfers
+        let balances = vec![7, 5, 3, -8, -7]
+            .into_iter()
+            .enumerate()
+            .map(|(id, balance)| (MemberId(id as u32), balance))
+            .collect();
+        assert_eq!(settled(&balances), 3);
+    }
+
+    #[test]
+    fn settle_up_single_group() {
+        let balances = vec![6, 4, -5, -3, -2]
+            .into_iter()
+            .enumerate()
+            .map(|(id, balance)| (MemberId(id as u32), balance))
+            .collect();
+        assert_eq!(settled(&balances), 4);
+    }
+}
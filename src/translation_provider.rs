@@ -1,4 +1,129 @@
+use crate::placeholders::placeholders;
+use log::debug;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
 include!(concat!(
     env!("OUT_DIR"),
     "/translation_provider_generated.rs"
-));
\ No newline at end of file
+));
+
+// extra languages can be dropped there, without rebuilding
+// a file can't replace one of the languages built in the program
+fn user_translations_dir() -> PathBuf {
+    let mut dir = PathBuf::from(crate::DATA_DIR);
+    dir.push("translations");
+    dir
+}
+
+impl TranslationProvider {
+    pub fn get_provider(locale_id: &str) -> Option<TranslationProvider> {
+        if let Some(content) = TranslationProvider::get_builtin_content(locale_id) {
+            return Some(
+                serde_json::from_str(content)
+                    .expect("TranslationProvider construction failed - the build is corrupted!"),
+            );
+        }
+        load_user_translations()
+            .into_iter()
+            .find(|translation| translation.id() == locale_id)
+    }
+
+    pub fn get_language_list() -> Vec<(String, String)> {
+        let mut language_list = TranslationProvider::get_builtin_language_list();
+        for translation in load_user_translations() {
+            if !language_list.iter().any(|(id, _)| *id == translation.id()) {
+                language_list.push((translation.id(), translation.display()));
+            }
+        }
+        language_list
+    }
+}
+
+// the files which can't be used are skipped, the reason being logged
+fn load_user_translations() -> Vec<TranslationProvider> {
+    let entries = match std::fs::read_dir(user_translations_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut translations = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+        match std::fs::read_to_string(&path)
+            .map_err(Box::<dyn std::error::Error>::from)
+            .and_then(|content| parse_user_translation(&content))
+        {
+            Ok(translation) => translations.push(translation),
+            Err(e) => debug!("Ignoring the translation file {:?}: {}", path, e),
+        }
+    }
+    translations
+}
+
+// checked against the keys of the generated struct: the unknown keys are dropped and the
+// missing ones (or the ones which aren't strings) are taken from the fallback language, only the
+// id and the display name being mandatory
+// the formatted strings not using exactly their declared placeholders are taken from the fallback
+// language as well, formatting them would fail
+fn parse_user_translation(
+    content: &str,
+) -> Result<TranslationProvider, Box<dyn std::error::Error>> {
+    let mut translation: Map<String, Value> = serde_json::from_str(content)?;
+    let fallback: Map<String, Value> =
+        serde_json::from_str(TranslationProvider::get_fallback_content())
+            .expect("The fallback translation is corrupted!");
+    let keys = TranslationProvider::get_keys();
+
+    for mandatory_key in &["id", "display"] {
+        if !translation
+            .get(*mandatory_key)
+            .map_or(false, Value::is_string)
+        {
+            return Err(format!("the \"{}\" key is missing", mandatory_key).into());
+        }
+    }
+    let unknown_keys = translation
+        .keys()
+        .filter(|key| !keys.contains(&key.as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    for key in unknown_keys {
+        debug!("Unknown translation key {}, ignoring it", key);
+        translation.remove(&key);
+    }
+    for key in keys {
+        if !translation.get(*key).map_or(false, Value::is_string) {
+            debug!("Missing translation key {}, using the fallback one", key);
+            translation.insert(
+                (*key).to_owned(),
+                fallback
+                    .get(*key)
+                    .cloned()
+                    .expect("The fallback translation misses a key!"),
+            );
+        }
+    }
+    for (key, declared) in TranslationProvider::get_formatted_keys() {
+        let used = placeholders(translation[*key].as_str().unwrap_or_default());
+        if used
+            .iter()
+            .any(|placeholder| !declared.contains(&placeholder.as_str()))
+            || declared
+                .iter()
+                .any(|placeholder| !used.iter().any(|used| used == placeholder))
+        {
+            debug!(
+                "Translation key {} doesn't use the placeholders {:?}, using the fallback one",
+                key, declared
+            );
+            translation.insert((*key).to_owned(), fallback[*key].clone());
+        }
+    }
+    Ok(serde_json::from_value(Value::Object(translation))?)
+}